@@ -0,0 +1,337 @@
+// 平面剖分（arrangement）：把一组可能相交、重叠的有向线段在交点处打断，合并重合的顶点和边，
+// 再用半边结构找出所有的面、面中的洞，以及每个面的环绕数。
+// 线段的 winding 是从它的右侧穿到左侧（orient > 0 的一侧）时环绕数的增量：
+// 按本项目约定的外环和洞，内部都在边的左侧，因此边界的 winding 都是 +1；只用来分隔区域的切割线为 0。
+// 无界面的环绕数为 0。
+
+use std::collections::HashMap;
+use std::iter;
+
+use crate::geom::{close_ring, orient_f64, ring_vertices, to_f64, to_point};
+use crate::Polygen;
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Segment {
+    pub from: [f64; 2],
+    pub to: [f64; 2],
+    pub winding: i32,
+}
+
+// 打断、合并后的边，winding 按 from->to 的方向计
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub winding: i32,
+}
+
+pub(crate) struct Arrangement {
+    pub vertices: Vec<[f64; 2]>,
+    pub edges: Vec<Edge>,
+    // 每个顶点落在哪些输入线段上：(线段在 new 的参数中的下标, 顶点在线段上的参数)
+    pub vertex_segments: Vec<Vec<(usize, f64)>>,
+}
+
+// 面的外边界为逆时针，洞为顺时针，都是不闭合的顶点下标序列
+pub(crate) struct Face {
+    pub outer: Vec<usize>,
+    pub holes: Vec<Vec<usize>>,
+    pub winding: i32,
+}
+
+pub(crate) struct Faces {
+    pub faces: Vec<Face>,
+    // 每条边 [from->to 左侧的面, 右侧的面]，None 为无界面
+    pub edge_faces: Vec<[Option<usize>; 2]>,
+}
+
+impl Arrangement {
+    pub(crate) fn new(segments: &[Segment]) -> Arrangement {
+        let scale = segments.iter()
+            .flat_map(|s| s.from.iter().chain(s.to.iter()))
+            .fold(1.0f64, |m, v| m.max(v.abs()));
+        // 距离在 eps 以内的点视为同一个点
+        let eps = scale * 1e-9;
+        let kept: Vec<usize> = (0..segments.len()).filter(|&i| segments[i].from != segments[i].to).collect();
+        let segments: Vec<&Segment> = kept.iter().map(|&i| &segments[i]).collect();
+
+        // 每条线段上的切分点 (参数, 点)，按x坐标扫描只检查x范围重叠的线段对
+        let mut splits: Vec<Vec<SplitPoint>> = segments.iter().map(|s| vec![(0.0, s.from), (1.0, s.to)]).collect();
+        let min_x = |s: &Segment| s.from[0].min(s.to[0]);
+        let mut order: Vec<usize> = (0..segments.len()).collect();
+        order.sort_by(|&a, &b| min_x(segments[a]).total_cmp(&min_x(segments[b])));
+        for (k, &i) in order.iter().enumerate() {
+            let max_x = segments[i].from[0].max(segments[i].to[0]) + eps;
+            for &j in &order[k + 1..] {
+                if min_x(segments[j]) > max_x { break; }
+                let (on_i, on_j) = crossings(segments[i], segments[j], eps);
+                splits[i].extend(on_i);
+                splits[j].extend(on_j);
+            }
+        }
+
+        // 合并重合的顶点，同一对顶点之间的边合并为一条，winding 相加
+        let mut snapper = Snapper { eps, vertices: Vec::new(), grid: HashMap::new() };
+        let mut edge_index: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut edges: Vec<Edge> = Vec::new();
+        let mut vertex_segments: Vec<Vec<(usize, f64)>> = Vec::new();
+        for (k, (segment, points)) in segments.iter().zip(splits.iter_mut()).enumerate() {
+            points.sort_by(|p, q| p.0.total_cmp(&q.0));
+            let ids: Vec<usize> = points.iter().map(|&(_, p)| snapper.vertex(p)).collect();
+            vertex_segments.resize(snapper.vertices.len(), Vec::new());
+            for (&(t, _), &v) in points.iter().zip(&ids) {
+                vertex_segments[v].push((kept[k], t));
+            }
+            for w in ids.windows(2) {
+                let (u, v) = (w[0], w[1]);
+                if u == v { continue; }
+                let key = (u.min(v), u.max(v));
+                let winding = if u < v { segment.winding } else { -segment.winding };
+                match edge_index.get(&key) {
+                    Some(&e) => edges[e].winding += winding,
+                    None => {
+                        edge_index.insert(key, edges.len());
+                        edges.push(Edge { from: key.0, to: key.1, winding });
+                    }
+                }
+            }
+        }
+        Arrangement { vertices: snapper.vertices, edges, vertex_segments }
+    }
+
+    // 使用同样的顶点、另一组边（通常是筛选过的边）构造新的剖分
+    pub(crate) fn with_edges(&self, edges: Vec<Edge>) -> Arrangement {
+        Arrangement { vertices: self.vertices.clone(), edges, vertex_segments: self.vertex_segments.clone() }
+    }
+
+    pub(crate) fn faces(&self) -> Faces {
+        // 半边 2e 为 edges[e] 的 from->to 方向，2e+1 为反方向；半边所在的面在它的左侧
+        let half_edges = self.edges.len() * 2;
+        let origin = |h: usize| if h % 2 == 0 { self.edges[h / 2].from } else { self.edges[h / 2].to };
+        let angle = |h: usize| {
+            let (a, b) = (self.vertices[origin(h)], self.vertices[origin(h ^ 1)]);
+            (b[1] - a[1]).atan2(b[0] - a[0])
+        };
+        let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); self.vertices.len()];
+        for h in 0..half_edges { outgoing[origin(h)].push(h); }
+        let mut position = vec![0; half_edges];
+        for list in outgoing.iter_mut() {
+            list.sort_by(|&a, &b| angle(a).total_cmp(&angle(b)));
+            for (k, &h) in list.iter().enumerate() { position[h] = k; }
+        }
+        // 沿面的边界走：到达顶点后，从反向半边起按角度递增取下一条出边
+        let next = |h: usize| {
+            let twin = h ^ 1;
+            let list = &outgoing[origin(twin)];
+            list[(position[twin] + 1) % list.len()]
+        };
+
+        let mut cycle_of = vec![usize::MAX; half_edges];
+        let mut cycles: Vec<Vec<usize>> = Vec::new();
+        for h in 0..half_edges {
+            if cycle_of[h] != usize::MAX { continue; }
+            let mut cycle = Vec::new();
+            let mut cur = h;
+            loop {
+                cycle_of[cur] = cycles.len();
+                cycle.push(origin(cur));
+                cur = next(cur);
+                if cur == h { break; }
+            }
+            cycles.push(cycle);
+        }
+        let points: Vec<Vec<[f64; 2]>> = cycles.iter().map(|c| c.iter().map(|&v| self.vertices[v]).collect()).collect();
+        let areas: Vec<f64> = points.iter().map(|p| signed_area(p)).collect();
+
+        // 连通分量，用于判断一个分量的外边界被哪个面包含
+        let mut parent: Vec<usize> = (0..self.vertices.len()).collect();
+        for edge in &self.edges {
+            let (a, b) = (find(&mut parent, edge.from), find(&mut parent, edge.to));
+            parent[a] = b;
+        }
+        let component: Vec<usize> = cycles.iter().map(|c| find(&mut parent, c[0])).collect();
+
+        // 面积为正的回路是有界面的外边界，其余回路是连通分量的外边界，作为洞放进包含它的最小的面
+        let mut face_of_cycle: Vec<Option<usize>> = vec![None; cycles.len()];
+        let mut faces: Vec<Face> = Vec::new();
+        for (c, cycle) in cycles.iter().enumerate() {
+            if areas[c] > 0.0 {
+                face_of_cycle[c] = Some(faces.len());
+                faces.push(Face { outer: cycle.clone(), holes: Vec::new(), winding: 0 });
+            }
+        }
+        for c in 0..cycles.len() {
+            if areas[c] > 0.0 { continue; }
+            let probe = points[c][0];
+            let container = (0..cycles.len())
+                .filter(|&k| areas[k] > 0.0 && component[k] != component[c] && winding_number(&points[k], probe) != 0)
+                .min_by(|&a, &b| areas[a].total_cmp(&areas[b]));
+            if let Some(k) = container {
+                let face = face_of_cycle[k].unwrap();
+                face_of_cycle[c] = Some(face);
+                faces[face].holes.push(cycles[c].clone());
+            }
+        }
+        let edge_faces: Vec<[Option<usize>; 2]> = (0..self.edges.len())
+            .map(|e| [face_of_cycle[cycle_of[2 * e]], face_of_cycle[cycle_of[2 * e + 1]]])
+            .collect();
+
+        // 从无界面出发，穿过每条边时环绕数增加（或减少）该边的 winding。节点0为无界面，面 f 为 f+1
+        let node = |f: Option<usize>| f.map_or(0, |f| f + 1);
+        let mut adjacent: Vec<Vec<(usize, i32)>> = vec![Vec::new(); faces.len() + 1];
+        for (edge, [left, right]) in self.edges.iter().zip(&edge_faces) {
+            adjacent[node(*right)].push((node(*left), edge.winding));
+            adjacent[node(*left)].push((node(*right), -edge.winding));
+        }
+        let mut windings: Vec<Option<i32>> = vec![None; faces.len() + 1];
+        windings[0] = Some(0);
+        let mut queue = vec![0];
+        while let Some(cur) = queue.pop() {
+            let w = windings[cur].unwrap();
+            for &(other, delta) in &adjacent[cur] {
+                if windings[other].is_none() {
+                    windings[other] = Some(w + delta);
+                    queue.push(other);
+                }
+            }
+        }
+        for (f, face) in faces.iter_mut().enumerate() {
+            face.winding = windings[f + 1].unwrap_or(0);
+        }
+        Faces { faces, edge_faces }
+    }
+
+    // 只保留两侧内外不同的边，方向使内部在左侧，重新求面；inside 按面的环绕数判断是否在内部
+    pub(crate) fn dissolve(&self, inside: impl Fn(i32) -> bool) -> Vec<Polygen> {
+        let (arrangement, faces) = self.dissolved_faces(inside);
+        faces.iter().map(|face| arrangement.face_polygen(face)).collect()
+    }
+
+    // 与 dissolve 相同，但返回只含保留下来的边的剖分和结果面，顶点下标与 self 的一致
+    pub(crate) fn dissolved_faces(&self, inside: impl Fn(i32) -> bool) -> (Arrangement, Vec<Face>) {
+        let faces = self.faces();
+        let is_inside = |f: Option<usize>| inside(f.map_or(0, |f| faces.faces[f].winding));
+        let edges: Vec<Edge> = self.edges.iter().zip(&faces.edge_faces)
+            .filter(|(_, &[left, right])| is_inside(left) != is_inside(right))
+            .map(|(edge, &[left, _])| Edge { winding: if is_inside(left) { 1 } else { -1 }, ..*edge })
+            .collect();
+        let arrangement = self.with_edges(edges);
+        let faces = arrangement.faces().faces.into_iter().filter(|face| face.winding != 0).collect();
+        (arrangement, faces)
+    }
+
+    // 面转换为本项目的多边形格式：外环在前，之后是洞，都是闭合回路
+    pub(crate) fn face_polygen(&self, face: &Face) -> Polygen {
+        iter::once(&face.outer).chain(&face.holes)
+            .map(|ring| close_ring(ring.iter().map(|&v| to_point(self.vertices[v])).collect()))
+            .collect()
+    }
+}
+
+// 多边形所有回路的边，每条的 winding 相同
+pub(crate) fn polygen_segments(polygen: &Polygen, winding: i32) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    for path in polygen {
+        let vertices = ring_vertices(path);
+        for i in 0..vertices.len() {
+            segments.push(Segment { from: to_f64(vertices[i]), to: to_f64(vertices[(i + 1) % vertices.len()]), winding });
+        }
+    }
+    segments
+}
+
+// 线段上的切分点 (参数, 点)
+type SplitPoint = (f64, [f64; 2]);
+
+// 两条线段的公共点在各自线段上的 (参数, 点)。
+// 一条线段的端点落在另一条上（相接或共线重叠）时用该端点切分，否则求真正的交点
+fn crossings(s1: &Segment, s2: &Segment, eps: f64) -> (Vec<SplitPoint>, Vec<SplitPoint>) {
+    let (a, b, c, d) = (s1.from, s1.to, s2.from, s2.to);
+    let mut on1 = Vec::new();
+    let mut on2 = Vec::new();
+    for &p in &[c, d] {
+        if let Some(t) = param_on(a, b, p, eps) { on1.push((t, p)); }
+    }
+    for &p in &[a, b] {
+        if let Some(t) = param_on(c, d, p, eps) { on2.push((t, p)); }
+    }
+    if on1.is_empty() && on2.is_empty() {
+        let (o1, o2) = (orient_f64(a, b, c), orient_f64(a, b, d));
+        let (o3, o4) = (orient_f64(c, d, a), orient_f64(c, d, b));
+        if o1 * o2 < 0.0 && o3 * o4 < 0.0 {
+            let s = o3 / (o3 - o4);
+            let p = [a[0] + s * (b[0] - a[0]), a[1] + s * (b[1] - a[1])];
+            on1.push((s, p));
+            on2.push((o1 / (o1 - o2), p));
+        }
+    }
+    (on1, on2)
+}
+
+// p 到线段 ab 的距离不超过 eps 时，返回 p 在 ab 上的投影参数
+fn param_on(a: [f64; 2], b: [f64; 2], p: [f64; 2], eps: f64) -> Option<f64> {
+    let d = [b[0] - a[0], b[1] - a[1]];
+    let len2 = d[0] * d[0] + d[1] * d[1];
+    let t = (((p[0] - a[0]) * d[0] + (p[1] - a[1]) * d[1]) / len2).clamp(0.0, 1.0);
+    let q = [a[0] + t * d[0], a[1] + t * d[1]];
+    if (p[0] - q[0]).hypot(p[1] - q[1]) <= eps { Some(t) } else { None }
+}
+
+// 按网格查找距离在 eps 以内的已有顶点
+struct Snapper {
+    eps: f64,
+    vertices: Vec<[f64; 2]>,
+    grid: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl Snapper {
+    fn vertex(&mut self, p: [f64; 2]) -> usize {
+        let cell_size = self.eps * 2.0;
+        let cell = ((p[0] / cell_size).floor() as i64, (p[1] / cell_size).floor() as i64);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if let Some(list) = self.grid.get(&(cell.0 + dx, cell.1 + dy)) {
+                    for &i in list {
+                        let q = self.vertices[i];
+                        if (p[0] - q[0]).hypot(p[1] - q[1]) <= self.eps { return i; }
+                    }
+                }
+            }
+        }
+        self.vertices.push(p);
+        self.grid.entry(cell).or_default().push(self.vertices.len() - 1);
+        self.vertices.len() - 1
+    }
+}
+
+fn find(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+// 与 measure::path_signed_area 相同的约定，逆时针为正；points 不闭合
+fn signed_area(points: &[[f64; 2]]) -> f64 {
+    let n = points.len();
+    (0..n).map(|i| {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        b[0] * a[1] - a[0] * b[1]
+    }).sum::<f64>() / 2.0
+}
+
+// 与 geom::ring_winding_number 相同的规则；points 不闭合
+fn winding_number(points: &[[f64; 2]], p: [f64; 2]) -> i32 {
+    let n = points.len();
+    let mut winding = 0;
+    for i in 0..n {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        if a[1] <= p[1] {
+            if b[1] > p[1] && orient_f64(a, b, p) < 0.0 { winding -= 1; }
+        } else if b[1] <= p[1] && orient_f64(a, b, p) > 0.0 {
+            winding += 1;
+        }
+    }
+    winding
+}
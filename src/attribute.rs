@@ -0,0 +1,69 @@
+// 顶点属性（高程 Z、测量值 M、颜色、纹理坐标等）随裁剪传递。
+// 属性与回路一一对应，每个回路给出各顶点的属性（不含闭合的末点）；
+// 结果中来自输入顶点的点直接沿用该顶点的属性，交点在所在的边上按参数线性插值，
+// 两个输入各自插值得到的值由 AttributePriority 决定用哪一个。
+
+use crate::boolean::{boolean_with_sources, BooleanOp};
+use crate::{clipping, Polygen, VertexSource};
+
+pub trait Attribute: Clone {
+    // t 为0时得到 self，为1时得到 other
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Attribute for f32 {
+    fn lerp(&self, other: &f32, t: f32) -> f32 {
+        self + (other - self) * t
+    }
+}
+
+macro_rules! impl_attribute_for_array {
+    ($($n:expr),*) => {$(
+        impl Attribute for [f32; $n] {
+            fn lerp(&self, other: &[f32; $n], t: f32) -> [f32; $n] {
+                let mut result = *self;
+                for (r, o) in result.iter_mut().zip(other.iter()) {
+                    *r += (o - *r) * t;
+                }
+                result
+            }
+        }
+    )*};
+}
+impl_attribute_for_array!(2, 3, 4);
+
+// 交点处使用哪个输入的属性
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AttributePriority { Main, Clipper }
+
+// 按顶点来源求结果顶点的属性，sources 与 main、clipper 中的下标对应（例如 ClipResult::result_sources）
+pub fn interpolate_attributes<T: Attribute>(sources: &[Vec<VertexSource>], main: &[Vec<T>], clipper: &[Vec<T>], priority: AttributePriority) -> Vec<Vec<T>> {
+    let on_edge = |attributes: &[Vec<T>], ring: usize, edge: usize, param: f32| {
+        let ring = &attributes[ring];
+        ring[edge].lerp(&ring[(edge + 1) % ring.len()], param)
+    };
+    sources.iter().map(|ring_sources| {
+        ring_sources.iter().map(|&source| match source {
+            VertexSource::Main { ring, index } => main[ring][index].clone(),
+            VertexSource::Clipper { ring, index } => clipper[ring][index].clone(),
+            VertexSource::Intersection { main_ring, main_edge, main_param, clipper_ring, clipper_edge, clipper_param } => match priority {
+                AttributePriority::Main => on_edge(main, main_ring, main_edge, main_param),
+                AttributePriority::Clipper => on_edge(clipper, clipper_ring, clipper_edge, clipper_param),
+            },
+        }).collect()
+    }).collect()
+}
+
+// 带属性的裁剪，返回裁剪结果（与 clipping 的 result 相同）及其顶点属性
+pub fn clipping_with_attributes<T: Attribute>(polygen: &Polygen, polygen_attributes: &[Vec<T>], clipper: &Polygen, clipper_attributes: &[Vec<T>], priority: AttributePriority) -> (Polygen, Vec<Vec<T>>) {
+    let result = clipping(polygen, clipper);
+    let attributes = interpolate_attributes(&result.result_sources, polygen_attributes, clipper_attributes, priority);
+    (result.result, attributes)
+}
+
+// 带属性的布尔运算
+pub fn boolean_with_attributes<T: Attribute>(a: &Polygen, a_attributes: &[Vec<T>], b: &Polygen, b_attributes: &[Vec<T>], op: BooleanOp, priority: AttributePriority) -> (Polygen, Vec<Vec<T>>) {
+    let (result, sources) = boolean_with_sources(a, b, op);
+    let attributes = interpolate_attributes(&sources, a_attributes, b_attributes, priority);
+    (result, attributes)
+}
@@ -0,0 +1,151 @@
+// 布尔运算（交、并、差）以及多个多边形的级联合并。
+// 两个多边形的边放进同一个平面剖分，在交点处打断，重合的边合并；每个面分别记下它在 a 和 b 中的环绕数，
+// 按运算选出结果区域，只保留结果区域与其余区域之间的边，重新组织成外环逆时针、洞顺时针的多边形。
+// 因此共边、共顶点以及完全重合的输入（例如同一图层中相邻的地块）都能正确处理。环绕数不为0的区域算作内部。
+
+use std::iter;
+
+use crate::arrangement::{polygen_segments, Arrangement, Segment};
+use crate::geom::{ring_vertices, to_f64, to_point};
+use crate::measure::{polygen_bounding_box, BoundingBox};
+use crate::{EdgeRef, Point, Polygen, VertexSource};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BooleanOp { Intersection, Union, Difference }
+
+// b 的边的 winding 取这个值，面的环绕数为 a 的环绕数加上 b 的环绕数乘以它，两部分可以分开
+const CLIPPER_WINDING: i32 = 1 << 16;
+
+pub fn boolean(a: &Polygen, b: &Polygen, op: BooleanOp) -> Polygen {
+    Arrangement::new(&boolean_segments(a, b)).dissolve(inside(op)).into_iter().flatten().collect()
+}
+
+// 同时返回每个结果顶点的来源，a 对应 VertexSource::Main，b 对应 VertexSource::Clipper，下标都是相对于调用方传入的回路。
+// 剖分记录了每个顶点落在哪些输入边上以及在边上的参数：是输入顶点时取该顶点（两者都有时取 a 的），
+// 否则是 a 的边与 b 的边的交点
+pub fn boolean_with_sources(a: &Polygen, b: &Polygen, op: BooleanOp) -> (Polygen, Vec<Vec<VertexSource>>) {
+    let arrangement = Arrangement::new(&boolean_segments(a, b));
+    let (dissolved, faces) = arrangement.dissolved_faces(inside(op));
+    // 线段下标到输入边，顺序与 polygen_segments 相同
+    let edge_refs = |polygen: &Polygen| -> Vec<EdgeRef> {
+        polygen.iter().enumerate()
+            .flat_map(|(ring, path)| (0..ring_vertices(path).len()).map(move |edge| EdgeRef { ring, edge }))
+            .collect()
+    };
+    let (main_edges, clipper_edges) = (edge_refs(a), edge_refs(b));
+    let mut result = Vec::new();
+    let mut sources = Vec::new();
+    for face in &faces {
+        result.extend(dissolved.face_polygen(face));
+        for ring in iter::once(&face.outer).chain(&face.holes) {
+            let mut ring_sources: Vec<VertexSource> = ring.iter().map(|&v| {
+                let on_edges = &arrangement.vertex_segments[v];
+                vertex_source(a, b, &main_edges, &clipper_edges, on_edges, to_point(arrangement.vertices[v]))
+            }).collect();
+            ring_sources.push(ring_sources[0]);
+            sources.push(ring_sources);
+        }
+    }
+    (result, sources)
+}
+
+// 先是 a 的所有边，winding 为1，然后是 b 的所有边
+fn boolean_segments(a: &Polygen, b: &Polygen) -> Vec<Segment> {
+    let mut segments = polygen_segments(a, 1);
+    segments.extend(polygen_segments(b, CLIPPER_WINDING));
+    segments
+}
+
+fn inside(op: BooleanOp) -> impl Fn(i32) -> bool {
+    move |winding: i32| {
+        let wb = (winding + CLIPPER_WINDING / 2).div_euclid(CLIPPER_WINDING);
+        let (in_a, in_b) = (winding - wb * CLIPPER_WINDING != 0, wb != 0);
+        match op {
+            BooleanOp::Intersection => in_a && in_b,
+            BooleanOp::Union => in_a || in_b,
+            BooleanOp::Difference => in_a && !in_b,
+        }
+    }
+}
+
+// on_edges 是剖分顶点所在的 (线段下标, 参数)，线段下标小于 main_edges.len() 的是 a 的边
+fn vertex_source(a: &Polygen, b: &Polygen, main_edges: &[EdgeRef], clipper_edges: &[EdgeRef],
+                 on_edges: &[(usize, f64)], point: Point) -> VertexSource {
+    let main: Vec<(EdgeRef, f64)> = on_edges.iter()
+        .filter(|&&(s, _)| s < main_edges.len())
+        .map(|&(s, t)| (main_edges[s], t))
+        .collect();
+    let clipper: Vec<(EdgeRef, f64)> = on_edges.iter()
+        .filter(|&&(s, _)| s >= main_edges.len())
+        .map(|&(s, t)| (clipper_edges[s - main_edges.len()], t))
+        .collect();
+    // 参数为0或1时就是边的端点
+    let endpoint = |polygen: &Polygen, on: &[(EdgeRef, f64)]| on.iter().find_map(|&(e, t)| {
+        let n = ring_vertices(&polygen[e.ring]).len();
+        if t == 0.0 { Some((e.ring, e.edge)) } else if t == 1.0 { Some((e.ring, (e.edge + 1) % n)) } else { None }
+    });
+    if let Some((ring, index)) = endpoint(a, &main) { return VertexSource::Main { ring, index }; }
+    if let Some((ring, index)) = endpoint(b, &clipper) { return VertexSource::Clipper { ring, index }; }
+    // 同一个多边形自身的边相交时，另一个多边形没有经过这里的边，只能取离它最近的边
+    let (main_edge, main_param) = main.first().map_or_else(|| nearest_edge(a, point), |&(e, t)| (e, t as f32));
+    let (clipper_edge, clipper_param) = clipper.first().map_or_else(|| nearest_edge(b, point), |&(e, t)| (e, t as f32));
+    VertexSource::Intersection {
+        main_ring: main_edge.ring,
+        main_edge: main_edge.edge,
+        main_param,
+        clipper_ring: clipper_edge.ring,
+        clipper_edge: clipper_edge.edge,
+        clipper_param,
+    }
+}
+
+// 离 p 最近的边及 p 在边上的投影参数
+fn nearest_edge(polygen: &Polygen, p: Point) -> (EdgeRef, f32) {
+    let p = to_f64(p);
+    let mut best = (f64::INFINITY, EdgeRef { ring: 0, edge: 0 }, 0.0);
+    for (ring, path) in polygen.iter().enumerate() {
+        let vertices = ring_vertices(path);
+        for edge in 0..vertices.len() {
+            let (s, e) = (to_f64(vertices[edge]), to_f64(vertices[(edge + 1) % vertices.len()]));
+            let d = [e[0] - s[0], e[1] - s[1]];
+            let len2 = d[0] * d[0] + d[1] * d[1];
+            if len2 == 0.0 { continue; }
+            let t = (((p[0] - s[0]) * d[0] + (p[1] - s[1]) * d[1]) / len2).clamp(0.0, 1.0);
+            let distance = (p[0] - s[0] - t * d[0]).hypot(p[1] - s[1] - t * d[1]);
+            if distance < best.0 { best = (distance, EdgeRef { ring, edge }, t); }
+        }
+    }
+    (best.1, best.2 as f32)
+}
+
+// 多个多边形的并集。按包围盒中心沿较长的方向递归地二分，两两合并，
+// 使每次合并的两部分在空间上相邻、大小相当；包围盒不相交的两部分直接拼接，不需要求交点
+pub fn cascaded_union(polygens: &[Polygen]) -> Polygen {
+    let mut items: Vec<(BoundingBox, Polygen)> = polygens.iter()
+        .filter_map(|p| polygen_bounding_box(p).map(|bbox| (bbox, p.clone())))
+        .collect();
+    match union_tree(&mut items) {
+        Some((_, polygen)) => polygen,
+        None => Vec::new(),
+    }
+}
+
+fn union_tree(items: &mut [(BoundingBox, Polygen)]) -> Option<(BoundingBox, Polygen)> {
+    match items.len() {
+        0 => return None,
+        1 => return Some(items[0].clone()),
+        _ => (),
+    }
+    let bounds = items.iter().map(|(bbox, _)| *bbox).reduce(|a, b| a.union(&b)).unwrap();
+    let axis = if bounds.width() >= bounds.height() { 0 } else { 1 };
+    items.sort_by(|a, b| a.0.center().position[axis].total_cmp(&b.0.center().position[axis]));
+    let (left, right) = items.split_at_mut(items.len() / 2);
+    let (left_bbox, left) = union_tree(left)?;
+    let (right_bbox, right) = union_tree(right)?;
+    let merged = if left_bbox.intersects(&right_bbox) {
+        boolean(&left, &right, BooleanOp::Union)
+    } else {
+        left.into_iter().chain(right).collect()
+    };
+    Some((left_bbox.union(&right_bbox), merged))
+}
@@ -0,0 +1,97 @@
+// 规范化输出，使相同的几何形状总是得到完全相同的顶点序列，便于比较差异和做快照测试：
+// 外环逆时针、洞顺时针（按嵌套层数判断，不依赖输入的方向），每个回路从字典序最小的顶点开始，
+// 回路之间按顶点序列的字典序排列；-0.0 统一为 0.0。
+
+use std::cmp::Ordering;
+
+use crate::geom::{close_ring, ring_vertices, ring_winding_number};
+use crate::measure::path_signed_area;
+use crate::{ClipResult, Path, Point, Polygen, VertexSource};
+
+pub fn canonicalize(polygen: &Polygen) -> Polygen {
+    canonical_order(polygen).into_iter().map(|(path, _, _)| path).collect()
+}
+
+// 规范化后的回路，以及它在输入中的下标和每个顶点在输入回路中的下标（不含闭合的末点）
+fn canonical_order(polygen: &Polygen) -> Vec<(Path, usize, Vec<usize>)> {
+    let rings: Vec<(usize, Path)> = polygen.iter()
+        .map(|path| ring_vertices(path).iter().map(|p| normalize_zero(*p)).collect::<Path>())
+        .enumerate()
+        .filter(|(_, ring)| ring.len() >= 3)
+        .collect();
+    let mut result: Vec<(Path, usize, Vec<usize>)> = (0..rings.len()).map(|i| {
+        // 被奇数个其它回路包含的是洞
+        let probe = rings[i].1[0];
+        let depth = (0..rings.len()).filter(|&j| j != i && ring_winding_number(&rings[j].1, probe) != 0).count();
+        let order = canonical_ring_order(&rings[i].1, depth % 2 == 0);
+        let path = close_ring(order.iter().map(|&k| rings[i].1[k]).collect());
+        (path, rings[i].0, order)
+    }).collect();
+    result.sort_by(|a, b| compare_paths(&a.0, &b.0));
+    result
+}
+
+impl ClipResult {
+    // 规范化裁剪结果；main 和 clipper 中是不闭合的折线，只统一 -0.0 并排序
+    pub fn canonicalized(self) -> ClipResult {
+        let sort_open = |polygen: Polygen| {
+            let mut paths: Polygen = polygen.into_iter()
+                .map(|path| path.into_iter().map(normalize_zero).collect())
+                .collect();
+            paths.sort_by(compare_paths);
+            paths
+        };
+        // 顶点来源随顶点一起重排
+        let (result, result_sources) = canonical_order(&self.result).into_iter()
+            .map(|(path, ring, order)| {
+                let sources = &self.result_sources[ring];
+                let mut ring_sources: Vec<VertexSource> = order.iter().map(|&k| sources[k]).collect();
+                ring_sources.push(ring_sources[0]);
+                (path, ring_sources)
+            })
+            .unzip();
+        ClipResult { main: sort_open(self.main), clipper: sort_open(self.clipper), result, result_sources }
+    }
+}
+
+// 单个回路：anti_clockwise 指定输出方向
+pub fn canonicalize_path(path: &Path, anti_clockwise: bool) -> Path {
+    let ring: Path = ring_vertices(path).iter().map(|p| normalize_zero(*p)).collect();
+    canonicalize_ring(&ring, anti_clockwise)
+}
+
+fn canonicalize_ring(ring: &[Point], anti_clockwise: bool) -> Path {
+    close_ring(canonical_ring_order(ring, anti_clockwise).iter().map(|&k| ring[k]).collect())
+}
+
+// 规范化后回路中各顶点在原回路中的下标
+fn canonical_ring_order(ring: &[Point], anti_clockwise: bool) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..ring.len()).collect();
+    if order.is_empty() { return order; }
+    if (path_signed_area(&ring.to_vec()) > 0.0) != anti_clockwise { order.reverse(); }
+    // 最小顶点出现多次（回路自身相接）时，取旋转后整个序列最小的那个
+    let n = order.len();
+    let start = (0..n).min_by(|&a, &b| {
+        (0..n).map(|k| compare_points(&ring[order[(a + k) % n]], &ring[order[(b + k) % n]]))
+            .find(|&o| o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }).unwrap();
+    order.rotate_left(start);
+    order
+}
+
+fn normalize_zero(p: Point) -> Point {
+    // 加0.0可以把 -0.0 变成 0.0，其它值不变
+    Point::new([p.position[0] + 0.0, p.position[1] + 0.0])
+}
+
+fn compare_points(a: &Point, b: &Point) -> Ordering {
+    a.position[0].total_cmp(&b.position[0]).then(a.position[1].total_cmp(&b.position[1]))
+}
+
+fn compare_paths(a: &Path, b: &Path) -> Ordering {
+    a.iter().zip(b.iter())
+        .map(|(p, q)| compare_points(p, q))
+        .find(|&o| o != Ordering::Equal)
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
@@ -0,0 +1,73 @@
+// 裁剪结果的后处理：合并距离在 epsilon 以内的相邻顶点，去掉共线顶点和尖刺，删除面积过小的回路。
+// 输出的回路仍按本项目的约定首尾闭合（末点与首点相同），不会出现其它重复点。
+
+use crate::geom::{close_ring, distance, orient, ring_vertices};
+use crate::measure::path_signed_area;
+use crate::{Path, Point, Polygen};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CleanupOptions {
+    // 距离小于等于该值的相邻顶点合并；顶点到前后两点连线的距离小于等于该值时视为共线
+    pub epsilon: f32,
+    // 是否删除共线（不改变形状的）顶点；折返形成的尖刺总会被删除
+    pub remove_collinear: bool,
+    // 面积（绝对值）小于等于该值的回路被删除，为0时只删除零面积回路
+    pub min_area: f32,
+}
+
+impl Default for CleanupOptions {
+    fn default() -> CleanupOptions {
+        CleanupOptions { epsilon: 1e-4, remove_collinear: true, min_area: 0.0 }
+    }
+}
+
+pub fn cleanup(polygen: &Polygen, options: &CleanupOptions) -> Polygen {
+    polygen.iter().filter_map(|path| cleanup_path(path, options)).collect()
+}
+
+// 回路退化（不足3个顶点或面积过小）时返回 None
+pub fn cleanup_path(path: &Path, options: &CleanupOptions) -> Option<Path> {
+    let epsilon = options.epsilon as f64;
+    let mut ring: Vec<Point> = Vec::new();
+    for &p in ring_vertices(path) {
+        if ring.last().is_none_or(|&q| distance(p, q) > epsilon) { ring.push(p); }
+    }
+    while ring.len() > 1 && distance(ring[0], ring[ring.len() - 1]) <= epsilon { ring.pop(); }
+
+    let mut changed = true;
+    while changed && ring.len() >= 3 {
+        changed = false;
+        let mut i = 0;
+        while i < ring.len() && ring.len() >= 3 {
+            let n = ring.len();
+            let (a, b, c) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+            if is_removable(a, b, c, epsilon, options.remove_collinear) {
+                ring.remove(i);
+                changed = true;
+                // 删除后前一个顶点与后一个顶点可能重合，需要再合并一次
+                let n = ring.len();
+                if n >= 2 {
+                    let prev = (i + n - 1) % n;
+                    let next = i % n;
+                    if distance(ring[prev], ring[next]) <= epsilon { ring.remove(next); }
+                }
+            } else {
+                i += 1;
+            }
+        }
+    }
+    if ring.len() < 3 { return None; }
+    let ring = close_ring(ring);
+    if path_signed_area(&ring).abs() <= options.min_area { return None; }
+    Some(ring)
+}
+
+// b 到直线 ac 的距离不超过 epsilon 时：折返（尖刺）总是删除，同向（共线）按选项删除
+fn is_removable(a: Point, b: Point, c: Point, epsilon: f64, remove_collinear: bool) -> bool {
+    let base = distance(a, c);
+    if base <= epsilon { return true; } // a、c 重合，b 是尖刺的顶端
+    if orient(a, b, c).abs() / base > epsilon { return false; }
+    let (a, b, c) = (a.position, b.position, c.position);
+    let forward = (b[0] - a[0]) * (c[0] - b[0]) + (b[1] - a[1]) * (c[1] - b[1]) >= 0.0;
+    !forward || remove_collinear
+}
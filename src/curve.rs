@@ -0,0 +1,144 @@
+// 曲线输入：圆弧和二次/三次贝塞尔曲线，按给定的弦高误差自适应地展平为折线，然后就可以参与裁剪。
+// 每个输出顶点都记录它来自哪一段曲线以及在该段上的参数 t（0为段起点，1为段终点）。
+
+use crate::geom::{close_ring, point_segment_distance, to_f64, to_point};
+use crate::{Path, Point, Polygen};
+
+pub enum Segment {
+    Line { to: Point },
+    // 从当前点绕 center 转到 to，半径取当前点到圆心的距离；to 与当前点重合时为整圆。
+    // anti_clockwise 的含义与 is_path_anti_clockwise 相同（屏幕坐标系）
+    Arc { center: Point, to: Point, anti_clockwise: bool },
+    QuadBezier { control: Point, to: Point },
+    CubicBezier { control1: Point, control2: Point, to: Point },
+}
+
+// 由起点和若干段曲线组成的回路，终点与起点不重合时自动用直线闭合
+pub struct CurvePath {
+    pub start: Point,
+    pub segments: Vec<Segment>,
+}
+
+pub type CurvePolygen = Vec<CurvePath>;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VertexOrigin {
+    pub segment: usize,
+    pub t: f32,
+}
+
+pub struct FlattenResult {
+    pub polygen: Polygen,
+    // 与 polygen 一一对应，记录每个顶点的来源
+    pub origins: Vec<Vec<VertexOrigin>>,
+}
+
+// 递归细分的最大深度，防止 tolerance 过小时无限细分
+const MAX_DEPTH: u32 = 16;
+
+pub fn flatten(polygen: &CurvePolygen, tolerance: f32) -> FlattenResult {
+    let mut result = FlattenResult { polygen: Vec::new(), origins: Vec::new() };
+    for path in polygen {
+        let (ring, origins) = flatten_path(path, tolerance);
+        result.polygen.push(ring);
+        result.origins.push(origins);
+    }
+    result
+}
+
+// 展平单个回路，返回闭合的 Path 及每个顶点的来源（闭合点的来源与首点相同）
+pub fn flatten_path(path: &CurvePath, tolerance: f32) -> (Path, Vec<VertexOrigin>) {
+    let tolerance = (tolerance as f64).max(f64::EPSILON);
+    let mut points = vec![path.start];
+    let mut origins = vec![VertexOrigin { segment: 0, t: 0.0 }];
+    let mut current = path.start;
+    for (index, segment) in path.segments.iter().enumerate() {
+        let mut samples = Vec::new();
+        match *segment {
+            Segment::Line { to } => samples.push((1.0, to)),
+            Segment::Arc { center, to, anti_clockwise } => flatten_arc(current, center, to, anti_clockwise, tolerance, &mut samples),
+            Segment::QuadBezier { control, to } => flatten_bezier(&[current, control, to], 0.0, 1.0, tolerance, 0, &mut samples),
+            Segment::CubicBezier { control1, control2, to } => {
+                flatten_bezier(&[current, control1, control2, to], 0.0, 1.0, tolerance, 0, &mut samples)
+            }
+        }
+        for (t, p) in samples {
+            if p != points[points.len() - 1] {
+                points.push(p);
+                origins.push(VertexOrigin { segment: index, t: t as f32 });
+            }
+        }
+        current = points[points.len() - 1];
+    }
+    // 最后一段的终点回到起点时去掉它，由闭合点代替
+    if points.len() > 1 && points[points.len() - 1] == path.start {
+        points.pop();
+        origins.pop();
+    }
+    origins.push(origins[0]);
+    (close_ring(points), origins)
+}
+
+fn flatten_arc(from: Point, center: Point, to: Point, anti_clockwise: bool, tolerance: f64, samples: &mut Vec<(f64, Point)>) {
+    let (c, a, b) = (to_f64(center), to_f64(from), to_f64(to));
+    let radius = (a[0] - c[0]).hypot(a[1] - c[1]);
+    let start_angle = (a[1] - c[1]).atan2(a[0] - c[0]);
+    let end_angle = (b[1] - c[1]).atan2(b[0] - c[0]);
+    // 屏幕坐标系y轴向下，逆时针对应角度减小
+    let full = std::f64::consts::TAU;
+    let mut sweep = end_angle - start_angle;
+    if anti_clockwise {
+        while sweep >= 0.0 { sweep -= full; }
+    } else {
+        while sweep <= 0.0 { sweep += full; }
+    }
+    if from != to && sweep.abs() >= full { sweep -= full.copysign(sweep); }
+    // 弦高 r(1 - cos(θ/2)) 不超过 tolerance
+    let max_step = if tolerance >= radius { std::f64::consts::FRAC_PI_2 } else { 2.0 * (1.0 - tolerance / radius).acos() };
+    let count = (sweep.abs() / max_step).ceil().max(1.0) as usize;
+    for i in 1..=count {
+        let t = i as f64 / count as f64;
+        let p = if i == count { to } else {
+            let angle = start_angle + sweep * t;
+            to_point([c[0] + radius * angle.cos(), c[1] + radius * angle.sin()])
+        };
+        samples.push((t, p));
+    }
+}
+
+fn lerp(a: [f64; 2], b: [f64; 2], t: f64) -> [f64; 2] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+}
+
+// de Casteljau 细分，返回曲线在 t=1/2 处分成的两半的控制点
+fn split_half(controls: &[[f64; 2]]) -> (Vec<[f64; 2]>, Vec<[f64; 2]>) {
+    let mut left = vec![controls[0]];
+    let mut right = vec![controls[controls.len() - 1]];
+    let mut level = controls.to_vec();
+    while level.len() > 1 {
+        level = level.windows(2).map(|w| lerp(w[0], w[1], 0.5)).collect();
+        left.push(level[0]);
+        right.push(level[level.len() - 1]);
+    }
+    right.reverse();
+    (left, right)
+}
+
+// 曲线位于控制点的凸包内，因此控制点到弦的最大距离就是误差的上界
+fn flatten_bezier(controls: &[Point], t0: f64, t1: f64, tolerance: f64, depth: u32, samples: &mut Vec<(f64, Point)>) {
+    let chord = [controls[0], controls[controls.len() - 1]];
+    let flat = controls[1..controls.len() - 1].iter().all(|&p| point_segment_distance(p, &chord) <= tolerance);
+    if flat || depth >= MAX_DEPTH {
+        samples.push((t1, chord[1]));
+        return;
+    }
+    let exact: Vec<[f64; 2]> = controls.iter().map(|&p| to_f64(p)).collect();
+    let (left, right) = split_half(&exact);
+    let left: Vec<Point> = left.into_iter().map(to_point).collect();
+    let mut right: Vec<Point> = right.into_iter().map(to_point).collect();
+    // 保证两半在中点处严格相接
+    right[0] = left[left.len() - 1];
+    let mid = (t0 + t1) / 2.0;
+    flatten_bezier(&left, t0, mid, tolerance, depth + 1, samples);
+    flatten_bezier(&right, mid, t1, tolerance, depth + 1, samples);
+}
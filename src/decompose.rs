@@ -0,0 +1,248 @@
+// 多边形（可带洞）的三角剖分与凸分解。
+// 三角剖分：先把每个洞用一条桥接边连到外环上，得到一个弱简单回路，再用割耳法剖分。
+// 凸分解：Hertel–Mehlhorn，在三角剖分的基础上删去不必要的对角线（删后两端仍为凸顶点）。
+// 输出的每一块都是逆时针闭合回路，只使用原多边形的顶点，各块恰好拼成原多边形。
+
+use std::collections::HashMap;
+
+use crate::geom::{close_ring, distance, orient, ring_vertices, ring_winding_number, segments_intersect};
+use crate::measure::path_signed_area;
+use crate::{Path, Point, Polygen};
+
+// 带编号的顶点，编号相同说明是同一个原始顶点（桥接时会复制顶点）
+#[derive(Copy, Clone)]
+struct Vertex {
+    point: Point,
+    id: usize,
+}
+
+pub fn triangulate(polygen: &Polygen) -> Vec<Path> {
+    let (points, triangles) = triangulate_ids(polygen);
+    triangles.iter().map(|t| to_path(&points, t)).collect()
+}
+
+// optimize 为 false 时按三角剖分的顺序合并；为 true 时每次在所有可合并的对角线中
+// 挑选合并后两端顶点离“平角”最远的那条，使后续还能继续合并的机会更多，通常块数更少
+pub fn convex_decomposition(polygen: &Polygen, optimize: bool) -> Vec<Path> {
+    let (points, triangles) = triangulate_ids(polygen);
+    let mut pieces = triangles;
+    loop {
+        let candidate = if optimize { best_merge(&points, &pieces) } else { first_merge(&points, &pieces) };
+        match candidate {
+            Some((a, b, merged)) => {
+                let (hi, lo) = if a > b { (a, b) } else { (b, a) };
+                pieces.swap_remove(hi);
+                pieces[lo] = merged;
+            }
+            None => break,
+        }
+    }
+    pieces.iter().map(|piece| to_path(&points, piece)).collect()
+}
+
+fn to_path(points: &[Point], ids: &[usize]) -> Path {
+    close_ring(ids.iter().map(|&i| points[i]).collect())
+}
+
+// 返回所有原始顶点，以及用顶点编号表示的三角形
+fn triangulate_ids(polygen: &Polygen) -> (Vec<Point>, Vec<Vec<usize>>) {
+    let mut points = Vec::new();
+    let mut rings: Vec<Vec<Vertex>> = Vec::new();
+    for path in polygen {
+        let ring = clean_ring(path);
+        if ring.len() < 3 { continue; }
+        rings.push(ring.iter().map(|&point| {
+            points.push(point);
+            Vertex { point, id: points.len() - 1 }
+        }).collect());
+    }
+    let areas: Vec<f64> = rings.iter().map(|r| ring_area(r)).collect();
+
+    let mut triangles = Vec::new();
+    for shell in (0..rings.len()).filter(|&i| areas[i] > 0.0) {
+        // 洞归属于包含它的面积最小的外环
+        let mut holes: Vec<usize> = (0..rings.len()).filter(|&h| {
+            areas[h] < 0.0 && enclosing_shell(&rings, &areas, h) == Some(shell)
+        }).collect();
+        holes.sort_by(|&a, &b| max_x(&rings[b]).total_cmp(&max_x(&rings[a])));
+        let mut outer = rings[shell].clone();
+        for (k, &h) in holes.iter().enumerate() {
+            let obstacles: Vec<&Vec<Vertex>> = holes[k + 1..].iter().map(|&i| &rings[i]).collect();
+            outer = bridge_hole(&outer, &rings[h], &obstacles);
+        }
+        ear_clip(outer, &mut triangles);
+    }
+    (points, triangles)
+}
+
+// 去掉重复点和共线点
+fn clean_ring(path: &Path) -> Path {
+    let mut ring: Path = ring_vertices(path).to_vec();
+    ring.dedup();
+    while ring.len() > 1 && ring[0] == ring[ring.len() - 1] { ring.pop(); }
+    let mut changed = true;
+    while changed && ring.len() >= 3 {
+        changed = false;
+        let n = ring.len();
+        for i in 0..n {
+            if orient(ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]) == 0.0 {
+                ring.remove(i);
+                changed = true;
+                break;
+            }
+        }
+    }
+    ring
+}
+
+fn ring_path(ring: &[Vertex]) -> Path {
+    ring.iter().map(|v| v.point).collect()
+}
+
+fn ring_area(ring: &[Vertex]) -> f64 {
+    path_signed_area(&ring_path(ring)) as f64
+}
+
+fn max_x(ring: &[Vertex]) -> f32 {
+    ring.iter().map(|v| v.point.position[0]).fold(f32::MIN, f32::max)
+}
+
+fn enclosing_shell(rings: &[Vec<Vertex>], areas: &[f64], hole: usize) -> Option<usize> {
+    let probe = rings[hole][0].point;
+    (0..rings.len())
+        .filter(|&i| areas[i] > 0.0 && ring_winding_number(&ring_path(&rings[i]), probe) != 0)
+        .min_by(|&a, &b| areas[a].total_cmp(&areas[b]))
+}
+
+// 方向 v->p 是否位于顶点 v 处的内角之内（回路逆时针，内部在左侧）
+fn locally_inside(prev: Point, v: Point, next: Point, p: Point) -> bool {
+    if orient(prev, v, next) >= 0.0 {
+        orient(prev, v, p) > 0.0 && orient(v, next, p) > 0.0
+    } else {
+        orient(prev, v, p) > 0.0 || orient(v, next, p) > 0.0
+    }
+}
+
+// 线段 a-b 是否与回路上某条边相交（不计与 a、b 重合的端点）
+fn crosses_ring(a: Point, b: Point, ring: &[Vertex]) -> bool {
+    let n = ring.len();
+    (0..n).any(|i| {
+        let (c, d) = (ring[i].point, ring[(i + 1) % n].point);
+        c != a && c != b && d != a && d != b && segments_intersect(&[a, b], &[c, d])
+    })
+}
+
+// 用一条桥接边把洞接入外环：从洞上x最大的顶点出发，连向最近的可见外环顶点
+fn bridge_hole(outer: &[Vertex], hole: &[Vertex], obstacles: &[&Vec<Vertex>]) -> Vec<Vertex> {
+    let (m, _) = hole.iter().enumerate().max_by(|a, b| {
+        a.1.point.position[0].total_cmp(&b.1.point.position[0]).then(b.1.point.position[1].total_cmp(&a.1.point.position[1]))
+    }).unwrap();
+    let mp = hole[m].point;
+    let n = outer.len();
+    let mut candidates: Vec<usize> = (0..n).collect();
+    candidates.sort_by(|&a, &b| distance(mp, outer[a].point).total_cmp(&distance(mp, outer[b].point)));
+    let visible = candidates.iter().copied().find(|&k| {
+        let v = outer[k].point;
+        v != mp
+            && locally_inside(outer[(k + n - 1) % n].point, v, outer[(k + 1) % n].point, mp)
+            && !crosses_ring(mp, v, outer)
+            && !crosses_ring(mp, v, hole)
+            && !obstacles.iter().any(|o| crosses_ring(mp, v, o))
+    });
+    // 合法输入一定能找到可见顶点；找不到时退而求其次，连向最近的顶点
+    let k = visible.unwrap_or(candidates[0]);
+    let mut result = Vec::with_capacity(n + hole.len() + 2);
+    result.extend_from_slice(&outer[..=k]);
+    result.extend(hole[m..].iter().chain(hole[..=m].iter()).copied());
+    result.extend_from_slice(&outer[k..]);
+    result
+}
+
+// 点 p 是否在三角形 abc（逆时针）内部或边上
+fn in_triangle(a: Point, b: Point, c: Point, p: Point) -> bool {
+    orient(a, b, p) >= 0.0 && orient(b, c, p) >= 0.0 && orient(c, a, p) >= 0.0
+}
+
+fn is_ear(ring: &[Vertex], i: usize) -> bool {
+    let n = ring.len();
+    let (a, b, c) = (ring[(i + n - 1) % n].point, ring[i].point, ring[(i + 1) % n].point);
+    if orient(a, b, c) <= 0.0 { return false; }
+    ring.iter().all(|v| {
+        let p = v.point;
+        p == a || p == b || p == c || !in_triangle(a, b, c, p)
+    })
+}
+
+fn ear_clip(mut ring: Vec<Vertex>, triangles: &mut Vec<Vec<usize>>) {
+    while ring.len() > 3 {
+        let n = ring.len();
+        // 没有合法的耳朵时（输入本身不合法）退而求其次，切掉任一凸顶点，保证能结束
+        let i = (0..n).find(|&i| is_ear(&ring, i))
+            .or_else(|| (0..n).find(|&i| orient(ring[(i + n - 1) % n].point, ring[i].point, ring[(i + 1) % n].point) > 0.0))
+            .unwrap_or(0);
+        let (a, b, c) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+        if orient(a.point, b.point, c.point) > 0.0 { triangles.push(vec![a.id, b.id, c.id]); }
+        ring.remove(i);
+    }
+    if ring.len() == 3 && orient(ring[0].point, ring[1].point, ring[2].point) > 0.0 {
+        triangles.push(ring.iter().map(|v| v.id).collect());
+    }
+}
+
+// 两块沿公共边 u-v 合并，a 中为 u->v，b 中为 v->u
+fn merge_along(a: &[usize], b: &[usize], u: usize, v: usize) -> Vec<usize> {
+    let ia = a.iter().position(|&x| x == v).unwrap();
+    let ib = b.iter().position(|&x| x == u).unwrap();
+    let mut merged: Vec<usize> = (0..a.len()).map(|k| a[(ia + k) % a.len()]).collect(); // v ... u
+    merged.extend((1..b.len() - 1).map(|k| b[(ib + k) % b.len()])); // u 之后、v 之前的部分
+    merged
+}
+
+// 合并后顶点 id 处转角的正弦，>= 0 说明仍是凸顶点
+fn turn_at(points: &[Point], piece: &[usize], id: usize) -> f64 {
+    let n = piece.len();
+    let k = piece.iter().position(|&x| x == id).unwrap();
+    let (a, b, c) = (points[piece[(k + n - 1) % n]], points[piece[k]], points[piece[(k + 1) % n]]);
+    orient(a, b, c) / (distance(a, b) * distance(b, c))
+}
+
+// 所有可以删除的对角线：(块a, 块b, 合并结果, 合并后两端转向的较小值)
+fn merges(points: &[Point], pieces: &[Vec<usize>], stop_at_first: bool) -> Vec<(usize, usize, Vec<usize>, f64)> {
+    let mut edges = HashMap::new();
+    for (p, piece) in pieces.iter().enumerate() {
+        for k in 0..piece.len() {
+            edges.insert((piece[k], piece[(k + 1) % piece.len()]), p);
+        }
+    }
+    let mut result = Vec::new();
+    for (a, piece) in pieces.iter().enumerate() {
+        for k in 0..piece.len() {
+            let (u, v) = (piece[k], piece[(k + 1) % piece.len()]);
+            let b = match edges.get(&(v, u)) {
+                Some(&b) if b > a => b,
+                _ => continue,
+            };
+            let merged = merge_along(piece, &pieces[b], u, v);
+            let slack = turn_at(points, &merged, u).min(turn_at(points, &merged, v));
+            // 同一原始顶点在合并结果中出现两次（例如洞被完全包围）时不合并
+            let mut ids = merged.clone();
+            ids.sort_unstable();
+            ids.dedup();
+            if slack >= 0.0 && ids.len() == merged.len() {
+                result.push((a, b, merged, slack));
+                if stop_at_first { return result; }
+            }
+        }
+    }
+    result
+}
+
+fn first_merge(points: &[Point], pieces: &[Vec<usize>]) -> Option<(usize, usize, Vec<usize>)> {
+    merges(points, pieces, true).pop().map(|(a, b, merged, _)| (a, b, merged))
+}
+
+fn best_merge(points: &[Point], pieces: &[Vec<usize>]) -> Option<(usize, usize, Vec<usize>)> {
+    merges(points, pieces, false).into_iter()
+        .max_by(|x, y| x.3.total_cmp(&y.3))
+        .map(|(a, b, merged, _)| (a, b, merged))
+}
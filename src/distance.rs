@@ -0,0 +1,102 @@
+// 距离查询：点到多边形边界的有向距离（在内部为负），两个多边形之间的最小距离，均给出最近点对。
+
+use crate::geom::{closest_point_on_segment, crossing_point, distance, ring_vertices, segments_intersect};
+use crate::query::{contains, Location};
+use crate::{Line, Point, Polygen};
+
+// 点到线段的距离及线段上的最近点
+pub fn point_line_distance(point: Point, line: &Line) -> (f32, Point) {
+    let (closest, _) = closest_point_on_segment(point, line);
+    (distance(point, closest) as f32, closest)
+}
+
+// 两条线段之间的距离及最近点对（第一个点在 l1 上），相交时距离为0、两点均为交点
+pub fn line_distance(l1: &Line, l2: &Line) -> (f32, Point, Point) {
+    let (d, p1, p2) = line_distance_f64(l1, l2);
+    (d as f32, p1, p2)
+}
+
+fn line_distance_f64(l1: &Line, l2: &Line) -> (f64, Point, Point) {
+    if segments_intersect(l1, l2) {
+        let p = crossing_point(l1, l2);
+        return (0.0, p, p);
+    }
+    let mut best = (f64::INFINITY, l1[0], l2[0]);
+    for &p in l1 {
+        let (q, _) = closest_point_on_segment(p, l2);
+        let d = distance(p, q);
+        if d < best.0 { best = (d, p, q); }
+    }
+    for &q in l2 {
+        let (p, _) = closest_point_on_segment(q, l1);
+        let d = distance(p, q);
+        if d < best.0 { best = (d, p, q); }
+    }
+    best
+}
+
+fn polygen_edges(polygen: &Polygen) -> Vec<Line> {
+    polygen.iter().flat_map(|path| {
+        let vertices = ring_vertices(path);
+        (0..vertices.len()).map(move |i| [vertices[i], vertices[(i + 1) % vertices.len()]])
+    }).collect()
+}
+
+// 点到多边形边界（含洞的边界）的有向距离及边界上的最近点，点在多边形内部时距离为负。
+// 多边形为空时返回 None
+pub fn signed_distance(polygen: &Polygen, point: Point) -> Option<(f32, Point)> {
+    let (d, closest) = polygen_edges(polygen).iter()
+        .map(|e| point_line_distance(point, e))
+        .min_by(|a, b| a.0.total_cmp(&b.0))?;
+    let sign = if contains(polygen, point) == Location::Inside { -1.0 } else { 1.0 };
+    Some((sign * d, closest))
+}
+
+// x方向的范围，用于剪枝
+fn x_range(line: &Line) -> (f64, f64) {
+    let (a, b) = (line[0].position[0] as f64, line[1].position[0] as f64);
+    (a.min(b), a.max(b))
+}
+
+fn y_range(line: &Line) -> (f64, f64) {
+    let (a, b) = (line[0].position[1] as f64, line[1].position[1] as f64);
+    (a.min(b), a.max(b))
+}
+
+// 两个多边形之间的最小距离及最近点对（第一个点在 a 上，第二个在 b 上）。
+// 边界相交或一个在另一个内部时距离为0。任一多边形为空时返回 None。
+// 把 b 的边按x坐标排序，只检查x范围与当前最优距离相容的边对，大回路也能较快求解
+pub fn polygen_distance(a: &Polygen, b: &Polygen) -> Option<(f32, Point, Point)> {
+    let edges_a = polygen_edges(a);
+    let mut edges_b = polygen_edges(b);
+    if edges_a.is_empty() || edges_b.is_empty() { return None; }
+    edges_b.sort_by(|l1, l2| x_range(l1).0.total_cmp(&x_range(l2).0));
+    let max_width_b = edges_b.iter().map(|e| x_range(e).1 - x_range(e).0).fold(0.0, f64::max);
+
+    let mut best = line_distance_f64(&edges_a[0], &edges_b[0]);
+    for ea in &edges_a {
+        let (ax0, ax1) = x_range(ea);
+        let (ay0, ay1) = y_range(ea);
+        let start = edges_b.partition_point(|e| x_range(e).0 < ax0 - best.0 - max_width_b);
+        for eb in &edges_b[start..] {
+            let (bx0, bx1) = x_range(eb);
+            if bx0 > ax1 + best.0 { break; }
+            let (by0, by1) = y_range(eb);
+            let gap_x = (bx0 - ax1).max(ax0 - bx1).max(0.0);
+            let gap_y = (by0 - ay1).max(ay0 - by1).max(0.0);
+            if gap_x.hypot(gap_y) >= best.0 { continue; }
+            let candidate = line_distance_f64(ea, eb);
+            if candidate.0 < best.0 { best = candidate; }
+        }
+        if best.0 == 0.0 { break; }
+    }
+
+    if best.0 > 0.0 {
+        // 边界不相交时，可能一个完全在另一个里面
+        let pa = edges_a[0][0];
+        if contains(b, pa) != Location::Outside { return Some((0.0, pa, pa)); }
+        let pb = edges_b[0][0];
+        if contains(a, pb) != Location::Outside { return Some((0.0, pb, pb)); }
+    }
+    Some((best.0 as f32, best.1, best.2))
+}
@@ -0,0 +1,106 @@
+// 各算法共用的基础几何谓词，统一用f64计算以减少误差
+
+use crate::{Line, Path, Point};
+
+pub(crate) fn to_f64(p: Point) -> [f64; 2] {
+    [p.position[0] as f64, p.position[1] as f64]
+}
+
+pub(crate) fn to_point(p: [f64; 2]) -> Point {
+    Point::new([p[0] as f32, p[1] as f32])
+}
+
+// a->b->c 为逆时针转向（与 is_path_anti_clockwise 的约定一致）时为正，共线为0
+pub(crate) fn orient(a: Point, b: Point, c: Point) -> f64 {
+    orient_f64(to_f64(a), to_f64(b), to_f64(c))
+}
+
+pub(crate) fn orient_f64(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[1] - a[1]) * (c[0] - a[0]) - (b[0] - a[0]) * (c[1] - a[1])
+}
+
+pub(crate) fn distance(a: Point, b: Point) -> f64 {
+    let (a, b) = (to_f64(a), to_f64(b));
+    (b[0] - a[0]).hypot(b[1] - a[1])
+}
+
+// 点到线段的最近点及其在线段上的参数
+pub(crate) fn closest_point_on_segment(p: Point, line: &Line) -> (Point, f64) {
+    let (a, b, p) = (to_f64(line[0]), to_f64(line[1]), to_f64(p));
+    let d = [b[0] - a[0], b[1] - a[1]];
+    let len2 = d[0] * d[0] + d[1] * d[1];
+    let t = if len2 == 0.0 { 0.0 } else { (((p[0] - a[0]) * d[0] + (p[1] - a[1]) * d[1]) / len2).clamp(0.0, 1.0) };
+    (to_point([a[0] + t * d[0], a[1] + t * d[1]]), t)
+}
+
+pub(crate) fn point_segment_distance(p: Point, line: &Line) -> f64 {
+    distance(p, closest_point_on_segment(p, line).0)
+}
+
+// 点c在线段ab的包围盒内（配合共线判断使用）
+fn in_box(a: Point, b: Point, c: Point) -> bool {
+    let (a, b, c) = (a.position, b.position, c.position);
+    c[0] >= a[0].min(b[0]) && c[0] <= a[0].max(b[0]) && c[1] >= a[1].min(b[1]) && c[1] <= a[1].max(b[1])
+}
+
+pub(crate) fn point_on_segment(p: Point, line: &Line) -> bool {
+    orient(line[0], line[1], p) == 0.0 && in_box(line[0], line[1], p)
+}
+
+// 两闭线段是否有公共点（端点相接、共线重叠都算）
+pub(crate) fn segments_intersect(l1: &Line, l2: &Line) -> bool {
+    let (a, b, c, d) = (l1[0], l1[1], l2[0], l2[1]);
+    let d1 = orient(a, b, c);
+    let d2 = orient(a, b, d);
+    let d3 = orient(c, d, a);
+    let d4 = orient(c, d, b);
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0)) && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0)) {
+        return true;
+    }
+    (d1 == 0.0 && in_box(a, b, c)) || (d2 == 0.0 && in_box(a, b, d))
+        || (d3 == 0.0 && in_box(c, d, a)) || (d4 == 0.0 && in_box(c, d, b))
+}
+
+// 已知两线段相交，求一个公共点；共线重叠时取落在另一条线段上的端点
+pub(crate) fn crossing_point(l1: &Line, l2: &Line) -> Point {
+    let (oa, ob) = (orient(l2[0], l2[1], l1[0]), orient(l2[0], l2[1], l1[1]));
+    if oa != ob {
+        let s = oa / (oa - ob);
+        let (a, b) = (to_f64(l1[0]), to_f64(l1[1]));
+        return to_point([a[0] + s * (b[0] - a[0]), a[1] + s * (b[1] - a[1])]);
+    }
+    l1.iter().copied().find(|&p| point_on_segment(p, l2))
+        .or_else(|| l2.iter().copied().find(|&p| point_on_segment(p, l1)))
+        .unwrap_or(l1[0])
+}
+
+// 去掉闭合回路末尾重复的首点
+pub(crate) fn ring_vertices(path: &Path) -> &[Point] {
+    if path.len() > 1 && path[0] == path[path.len() - 1] { &path[..path.len() - 1] } else { &path[..] }
+}
+
+// 把顶点序列闭合成绘制约定下的回路
+pub(crate) fn close_ring(mut vertices: Path) -> Path {
+    if let Some(&first) = vertices.first() {
+        if vertices.last() != Some(&first) || vertices.len() == 1 { vertices.push(first); }
+    }
+    vertices
+}
+
+// 点相对单个回路的环绕数（点在边上时结果无意义，由调用方另行判断）
+pub(crate) fn ring_winding_number(path: &Path, p: Point) -> i32 {
+    let vertices = ring_vertices(path);
+    let n = vertices.len();
+    let y = p.position[1];
+    let mut winding = 0;
+    for i in 0..n {
+        let a = vertices[i];
+        let b = vertices[(i + 1) % n];
+        if a.position[1] <= y {
+            if b.position[1] > y && orient(a, b, p) < 0.0 { winding -= 1; }
+        } else if b.position[1] <= y && orient(a, b, p) > 0.0 {
+            winding += 1;
+        }
+    }
+    winding
+}
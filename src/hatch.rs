@@ -0,0 +1,148 @@
+// 填充图案：按给定角度和间距生成平行的填充线（绘图仪、CNC 的填充），截取多边形内部的部分。
+// clipping 只能裁剪闭合回路，因此填充线和折返的连线都用 polyline::clip_polyline 按线段与多边形的关系切分。
+// 填充线位于 n·p = k * spacing（n 为填充方向的法向，k 为整数），相邻的多边形用同样的参数填充时线是对齐的。
+// 输出的各条折线按空行程（上一条的终点到下一条的起点）尽量短的顺序排列，必要时反向，用贪心的最近邻方法。
+
+use crate::geom::{to_f64, to_point};
+use crate::measure::polygen_bounding_box;
+use crate::polyline::clip_polyline;
+use crate::{Path, Polygen};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HatchPattern {
+    // 一组平行线
+    Parallel,
+    // 两组互相垂直的平行线
+    CrossHatch,
+    // 平行线的相邻两条在端点处用多边形内部的斜线连起来，来回折返成连续的折线；
+    // 连线不超过两倍间距，否则断开，避免长距离斜穿已经填过的区域
+    ZigZag,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HatchOptions {
+    // 填充方向与x轴的夹角（弧度，从x轴转向y轴为正）
+    pub angle: f32,
+    // 相邻两条填充线的距离
+    pub spacing: f32,
+    pub pattern: HatchPattern,
+}
+
+impl Default for HatchOptions {
+    fn default() -> HatchOptions {
+        HatchOptions { angle: 0.0, spacing: 1.0, pattern: HatchPattern::Parallel }
+    }
+}
+
+// 平行线和交叉线的每一段是两个点的折线，折返的图案每条是多个点的折线
+pub fn hatch(polygen: &Polygen, options: &HatchOptions) -> Vec<Path> {
+    let spacing = options.spacing as f64;
+    if spacing.is_nan() || spacing <= 0.0 { return Vec::new(); }
+    let angle = options.angle as f64;
+    let paths = match options.pattern {
+        HatchPattern::Parallel => hatch_lines(polygen, angle, spacing).into_iter().flatten().collect(),
+        HatchPattern::CrossHatch => {
+            let mut paths: Vec<Path> = hatch_lines(polygen, angle, spacing).into_iter().flatten().collect();
+            paths.extend(hatch_lines(polygen, angle + std::f64::consts::FRAC_PI_2, spacing).into_iter().flatten());
+            paths
+        }
+        HatchPattern::ZigZag => zigzag(polygen, hatch_lines(polygen, angle, spacing), spacing),
+    };
+    order_paths(paths)
+}
+
+// 每条填充线在多边形内的各段，沿填充方向排列；按线的顺序输出
+fn hatch_lines(polygen: &Polygen, angle: f64, spacing: f64) -> Vec<Vec<Path>> {
+    let bbox = match polygen_bounding_box(polygen) {
+        Some(bbox) => bbox,
+        None => return Vec::new(),
+    };
+    let (u, n) = ([angle.cos(), angle.sin()], [-angle.sin(), angle.cos()]);
+    let corners = [
+        [bbox.min[0] as f64, bbox.min[1] as f64], [bbox.max[0] as f64, bbox.min[1] as f64],
+        [bbox.min[0] as f64, bbox.max[1] as f64], [bbox.max[0] as f64, bbox.max[1] as f64],
+    ];
+    let project = |d: [f64; 2], p: &[f64; 2]| d[0] * p[0] + d[1] * p[1];
+    let range = |d: [f64; 2]| corners.iter().map(|p| project(d, p)).fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let (u_min, u_max) = range(u);
+    let (n_min, n_max) = range(n);
+    // 线段两端伸出包围盒之外
+    let (u_min, u_max) = (u_min - 1.0, u_max + 1.0);
+    let point = |s: f64, t: f64| to_point([u[0] * s + n[0] * t, u[1] * s + n[1] * t]);
+    ((n_min / spacing).ceil() as i64..=(n_max / spacing).floor() as i64).map(|k| {
+        let t = k as f64 * spacing;
+        let line = vec![point(u_min, t), point(u_max, t)];
+        clip_polyline(&line, polygen).inside.into_iter()
+            .map(|piece| piece.path)
+            .filter(|path| path.len() >= 2 && path[0] != path[path.len() - 1])
+            .collect()
+    }).collect()
+}
+
+// 从还没用过的最靠前的一段开始，每次在下一条线上找一段，用完全在多边形内、长度不超过两倍间距的连线
+// 接上离当前终点最近的端点
+fn zigzag(polygen: &Polygen, lines: Vec<Vec<Path>>, spacing: f64) -> Vec<Path> {
+    let mut used: Vec<Vec<bool>> = lines.iter().map(|pieces| vec![false; pieces.len()]).collect();
+    let mut result = Vec::new();
+    for first in 0..lines.len() {
+        for start in 0..lines[first].len() {
+            if used[first][start] { continue; }
+            used[first][start] = true;
+            let mut path = lines[first][start].clone();
+            let mut k = first + 1;
+            while k < lines.len() {
+                let end = to_f64(path[path.len() - 1]);
+                let mut best: Option<(f64, usize, bool)> = None;
+                for (i, piece) in lines[k].iter().enumerate() {
+                    if used[k][i] { continue; }
+                    for &reversed in &[false, true] {
+                        let p = to_f64(if reversed { piece[piece.len() - 1] } else { piece[0] });
+                        let d = (p[0] - end[0]).hypot(p[1] - end[1]);
+                        if d > 2.0 * spacing || best.is_some_and(|(bd, _, _)| d >= bd) { continue; }
+                        let connector = vec![to_point(end), to_point(p)];
+                        if clip_polyline(&connector, polygen).outside.is_empty() { best = Some((d, i, reversed)); }
+                    }
+                }
+                let (_, i, reversed) = match best {
+                    Some(best) => best,
+                    None => break,
+                };
+                used[k][i] = true;
+                let mut piece = lines[k][i].clone();
+                if reversed { piece.reverse(); }
+                path.extend(piece);
+                k += 1;
+            }
+            result.push(path);
+        }
+    }
+    result
+}
+
+// 贪心：从第一条开始，每次取起点或终点离当前位置最近的一条，终点更近时反向
+fn order_paths(mut paths: Vec<Path>) -> Vec<Path> {
+    let mut result: Vec<Path> = Vec::with_capacity(paths.len());
+    while !paths.is_empty() {
+        let next = match result.last() {
+            None => (0, false),
+            Some(last) => {
+                let end = to_f64(last[last.len() - 1]);
+                let distance = |p| {
+                    let p = to_f64(p);
+                    (p[0] - end[0]).hypot(p[1] - end[1])
+                };
+                let mut best = (f64::INFINITY, 0, false);
+                for (i, path) in paths.iter().enumerate() {
+                    let (d0, d1) = (distance(path[0]), distance(path[path.len() - 1]));
+                    if d0 < best.0 { best = (d0, i, false); }
+                    if d1 < best.0 { best = (d1, i, true); }
+                }
+                (best.1, best.2)
+            }
+        };
+        let mut path = paths.swap_remove(next.0);
+        if next.1 { path.reverse(); }
+        result.push(path);
+    }
+    result
+}
@@ -0,0 +1,41 @@
+// 凸包（Andrew 单调链算法）。
+// 结果为逆时针（与 is_path_anti_clockwise 的约定一致）的闭合回路，从字典序最小的顶点开始；
+// 重复点只算一次，凸包边上的共线点不作为顶点输出，因此同一组点无论输入顺序如何结果都相同。
+// 退化情况：没有点时返回空回路，只有一个不同的点时返回 [p, p]，所有点共线时返回 [a, b, a]。
+
+use crate::geom::{close_ring, orient};
+use crate::{Path, Point, Polygen};
+
+pub fn convex_hull(points: &[Point]) -> Path {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| {
+        a.position[0].total_cmp(&b.position[0]).then(a.position[1].total_cmp(&b.position[1]))
+    });
+    sorted.dedup();
+    if sorted.len() < 3 { return close_ring(sorted); }
+
+    let mut hull: Path = Vec::with_capacity(sorted.len() + 1);
+    // 先沿x增大方向求一侧的链，再反向求另一侧，只保留严格逆时针转向的顶点
+    for pass in 0..2 {
+        let chain_start = hull.len();
+        let iter: Box<dyn Iterator<Item = &Point>> = if pass == 0 { Box::new(sorted.iter()) } else { Box::new(sorted.iter().rev()) };
+        for &p in iter {
+            while hull.len() >= chain_start + 2 && orient(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop(); // 链的终点是另一条链的起点
+    }
+    close_ring(hull)
+}
+
+pub fn path_convex_hull(path: &Path) -> Path {
+    convex_hull(path)
+}
+
+// 洞一定在外环内部，不影响凸包，但一并计算也无妨
+pub fn polygen_convex_hull(polygen: &Polygen) -> Path {
+    let points: Vec<Point> = polygen.iter().flatten().copied().collect();
+    convex_hull(&points)
+}
@@ -0,0 +1,369 @@
+#[macro_use]
+extern crate glium;
+
+mod arrangement;
+pub mod attribute;
+pub mod boolean;
+pub mod canonical;
+pub mod cleanup;
+pub mod curve;
+pub mod decompose;
+pub mod distance;
+mod geom;
+pub mod hatch;
+pub mod hull;
+pub mod measure;
+pub mod multi;
+pub mod nfp;
+pub mod pocket;
+pub mod polyline;
+pub mod prepared;
+pub mod query;
+pub mod simplify;
+pub mod skeleton;
+pub mod split;
+pub mod stroke;
+pub mod tagged;
+pub mod trapezoid;
+pub mod valid;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Point {
+    pub position: [f32; 2],
+}
+implement_vertex!(Point, position);
+impl Point {
+    #[allow(clippy::redundant_field_names)]
+    pub fn new(position: [f32; 2]) -> Point {
+        Point { position: position }
+    }
+}
+
+pub type Polygen = Vec<Vec<Point>>;
+pub type Path = Vec<Point>;
+pub type Line = [Point; 2];
+
+// 按照绘制时的约定（屏幕坐标系，y轴向下），外环逆时针、内环顺时针
+#[allow(clippy::needless_return)]
+pub fn is_path_anti_clockwise(path: &Path) -> bool {
+    return measure::path_signed_area(path) > 0.0;
+}
+
+pub struct ClipResult {
+    pub main: Polygen,
+    pub clipper: Polygen,
+    pub result: Polygen,
+    // 与 result 一一对应，记录每个结果顶点的来源
+    pub result_sources: Vec<Vec<VertexSource>>,
+}
+
+#[allow(clippy::manual_range_contains)]
+pub fn intersection(l1: &Line, l2: &Line) -> Option<(Point, f32, f32, PointType)> {
+    let a = l1[0].position;
+    let b = l1[1].position;
+    let c = l2[0].position;
+    let d = l2[1].position;
+    let dir1 = [b[0] - a[0], b[1] - a[1]];
+    let dir2 = [d[0] - c[0], d[1] - c[1]];
+    #[allow(non_snake_case)] let D = a[0] * dir2[1] - b[0] * dir2[1] - c[0] * dir1[1] + d[0] * dir1[1];
+    if D == 0.0 { return None; } // 两直线平行则没有交点；重合也视为没有交点
+    let s = (a[0] * dir2[1] + c[0] * (a[1] - d[1]) + d[0] * (c[1] - a[1])) / D;
+    let t = -(a[0] * (c[1] - b[1]) + b[0] * (a[1] - c[1]) + c[0] * dir1[1]) / D;
+    if s >= 0.0 && s <= 1.0 && t >= 0.0 && t <= 1.0 {
+        // 根据叉乘推导出的
+        let m = dir2[0] * dir1[1] - dir1[0] * dir2[1];
+        let point_type = if m > 0.0 { PointType::Out } else { PointType::In };
+        Some((Point::new([a[0] + s * (b[0] - a[0]), a[1] + s * (b[1] - a[1])]), s, t, point_type))
+    } else { None }
+}
+
+// 输入中的一条边：回路下标，边下标（边 k 从顶点 k 到顶点 k+1，不计闭合的末点）
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EdgeRef {
+    pub ring: usize,
+    pub edge: usize,
+}
+
+#[derive(Clone)]
+pub enum PointType { OriginMain, OriginClipper, In, Out }
+
+// 结果顶点的来源。ring 是回路在输入中的下标，index 是回路中顶点的下标（不计闭合的末点）；
+// 交点记录两条相交的边，边 k 是从顶点 k 到顶点 k+1 的边，param 是交点在边上的参数（0为起点，1为终点）
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum VertexSource {
+    Main { ring: usize, index: usize },
+    Clipper { ring: usize, index: usize },
+    Intersection {
+        main_ring: usize,
+        main_edge: usize,
+        main_param: f32,
+        clipper_ring: usize,
+        clipper_edge: usize,
+        clipper_param: f32,
+    },
+}
+
+#[derive(Clone)]
+struct PointTableItem {
+    point: Point,
+    point_type: PointType,
+    param1: f32,
+    param2: f32,
+    next1: usize,
+    next2: usize,
+    is_result: bool,
+    source: VertexSource,
+}
+
+// 构建顶点表，返回 (顶点表, 主多边形顶点的结束位置, 裁剪多边形顶点的结束位置)
+#[allow(clippy::clone_on_copy)]
+fn build_point_table(polygen: &Polygen, clipper: &Polygen) -> (Vec<PointTableItem>, usize, usize) {
+    // 构建初始顶点表
+    let mut table = Vec::new();
+    let generate_point_table = |table: &mut Vec<PointTableItem>, polygen: &Polygen, point_type: PointType| {
+        for (ring, path) in polygen.iter().enumerate() {
+            let head_index = table.len();
+            for (i, point) in path[..path.len() - 1].iter().enumerate() {
+                let next_index = if i < path.len() - 2 { table.len() + 1 } else { head_index };
+                table.push(PointTableItem {
+                    point: point.clone(),
+                    point_type: point_type.clone(),
+                    param1: 1.0,
+                    param2: 1.0,
+                    next1: if let PointType::OriginMain = point_type { next_index } else { 0 },
+                    next2: if let PointType::OriginClipper = point_type { next_index } else { 0 },
+                    is_result: false,
+                    source: match point_type {
+                        PointType::OriginMain => VertexSource::Main { ring, index: i },
+                        _ => VertexSource::Clipper { ring, index: i },
+                    },
+                });
+            }
+        }
+    };
+    generate_point_table(&mut table, polygen, PointType::OriginMain);
+    let main_end = table.len();
+    generate_point_table(&mut table, clipper, PointType::OriginClipper);
+    let clipper_end = table.len();
+    let table_origin = table.clone();
+
+    // 寻找交点，插入顶点表
+    for i1 in 0..main_end {
+        for i2 in main_end..clipper_end {
+            let item1 = &table_origin[i1];
+            let item2 = &table_origin[i2];
+            let l1 = [item1.point, table_origin[item1.next1].point];
+            let l2 = [item2.point, table_origin[item2.next2].point];
+            let inter = intersection(&l1, &l2);
+            if let Some((pt, s, t, point_type)) = inter {
+                let source = match (item1.source, item2.source) {
+                    (VertexSource::Main { ring: main_ring, index: main_edge }, VertexSource::Clipper { ring: clipper_ring, index: clipper_edge }) =>
+                        VertexSource::Intersection { main_ring, main_edge, main_param: s, clipper_ring, clipper_edge, clipper_param: t },
+                    _ => unreachable!(),
+                };
+                let mut new_item = PointTableItem { point: pt.clone(), point_type: point_type.clone(), param1: s, param2: t, next1: 0, next2: 0, is_result: false, source };
+                // 寻找插入位置
+                let mut cur = i1;
+                while table[table[cur].next1].param1 < s {
+                    cur = table[cur].next1;
+                }
+                new_item.next1 = table[cur].next1;
+                table[cur].next1 = table.len();
+
+                let mut cur = i2;
+                while table[table[cur].next2].param2 < t {
+                    cur = table[cur].next2;
+                }
+                new_item.next2 = table[cur].next2;
+                table[cur].next2 = table.len();
+
+                table.push(new_item);
+            }
+        }
+    }
+    (table, main_end, clipper_end)
+}
+
+// 从没到达过的交点出发，沿顶点表走出结果回路：在 In 交点沿主多边形走、在 Out 交点沿裁剪多边形走，得到交集。
+// 同时返回每个结果顶点的来源
+#[allow(clippy::redundant_pattern_matching)]
+fn trace_result(table: &mut [PointTableItem]) -> (Polygen, Vec<Vec<VertexSource>>) {
+    let mut result = Vec::new();
+    let mut sources = Vec::new();
+    loop {
+        let mut start = None;
+        // 寻找没到达过的交点
+        for (i, item) in table.iter().enumerate() {
+            match item.point_type {
+                PointType::OriginMain => (),
+                PointType::OriginClipper => (),
+                _ => {
+                    if !item.is_result {
+                        start = Some(i);
+                        break;
+                    }
+                }
+            }
+        }
+        if let None = start { break; } // 找不到未处理的交点，算法完成
+        let start = start.unwrap();
+
+        let mut res = Vec::new();
+        let mut res_sources = Vec::new();
+        // 本回路中走过的顶点表下标及其在 res 中的位置
+        let mut visited = std::collections::HashMap::new();
+        let mut cur = start;
+        loop {
+            visited.insert(cur, res.len());
+            let item = &mut table[cur];
+            item.is_result = true;
+            res.push(item.point);
+            res_sources.push(item.source);
+            cur = match item.point_type {
+                PointType::OriginMain => item.next1,
+                PointType::OriginClipper => item.next2,
+                PointType::In => item.next1,
+                PointType::Out => item.next2,
+            };
+            // 退化输入（交点恰好落在顶点上等）可能使链表先走一段尾巴再进入一个不经过起点的环。
+            // 回到本回路走过的顶点时，去掉进环之前的尾巴，保留这个环；
+            // 走进之前的回路已经输出过的顶点时，这段尾巴不属于任何新回路，放弃它
+            if let Some(&first) = visited.get(&cur) {
+                res.drain(..first);
+                res_sources.drain(..first);
+                res.push(table[cur].point);
+                res_sources.push(table[cur].source);
+                break;
+            }
+            if table[cur].is_result {
+                res.clear();
+                break;
+            }
+        }
+        if !res.is_empty() {
+            result.push(res);
+            sources.push(res_sources);
+        }
+    };
+    (result, sources)
+}
+
+
+#[allow(clippy::redundant_pattern_matching, clippy::len_zero, clippy::single_match, clippy::collapsible_match)]
+pub fn clipping(polygen: &Polygen, clipper: &Polygen) -> ClipResult {
+    let (mut table, main_end, clipper_end) = build_point_table(polygen, clipper);
+
+    // 运行算法
+    let (result, result_sources) = trace_result(&mut table);
+
+    // 造访所有点，计算新的polygen和clipper
+    // 沿着主多边形顶点表，在每个环上走一次
+    let mut polygen = Vec::new();
+    loop {
+        let mut start = None;
+        // 寻找没到达过的顶点
+        for (i, item) in table[0..main_end].iter().enumerate() {
+            match item.point_type {
+                PointType::OriginMain => {
+                    if !item.is_result {
+                        start = Some(i);
+                        break;
+                    }
+                }
+                _ => ()
+            }
+        }
+        if let None = start { break; } // 找不到未到达的顶点，搜索完成
+        let start = start.unwrap();
+
+        let mut res = Vec::new();
+        let mut cur = start;
+        loop {
+            let item = &mut table[cur];
+            let is_edge = match item.point_type {
+                PointType::OriginMain => !item.is_result,
+                PointType::OriginClipper => {
+                    debug_assert!(false);
+                    false
+                }
+                PointType::In => false,
+                PointType::Out => true,
+            };
+            item.is_result = true;
+            let next_ptr = item.next1;
+
+            if is_edge {
+                if res.len() == 0 { res.push(item.point) }
+                res.push(table[next_ptr].point)
+            } else {
+                if res.len() > 0 {
+                    polygen.push(res);
+                    res = Vec::new();
+                }
+            }
+            cur = next_ptr;
+            if cur == start {
+                if res.len() > 0 {
+                    polygen.push(res);
+                }
+                break;
+            }
+        };
+    }
+
+    let mut clipper = Vec::new();
+    loop {
+        let mut start = None;
+        // 寻找没到达过的顶点
+        for (i, item) in table[main_end..clipper_end].iter().enumerate() {
+            match item.point_type {
+                PointType::OriginClipper => {
+                    if !item.is_result {
+                        start = Some(i);
+                        break;
+                    }
+                }
+                _ => ()
+            }
+        }
+        if let None = start { break; } // 找不到未到达的顶点，搜索完成
+        let start = start.unwrap() + main_end;
+
+        let mut res = Vec::new();
+        let mut cur = start;
+
+        loop {
+            let item = &mut table[cur];
+            let is_edge = match item.point_type {
+                PointType::OriginMain => {
+                    debug_assert!(false);
+                    false
+                }
+                PointType::OriginClipper => !item.is_result,
+                PointType::In => true,
+                PointType::Out => false,
+            };
+            item.is_result = true;
+            let next_ptr = item.next2;
+
+            if is_edge {
+                if res.len() == 0 { res.push(item.point) }
+                res.push(table[next_ptr].point)
+            } else {
+                if res.len() > 0 {
+                    clipper.push(res);
+                    res = Vec::new();
+                }
+            }
+            cur = next_ptr;
+            if cur == start {
+                if res.len() > 0 {
+                    clipper.push(res);
+                }
+                break;
+            }
+        };
+    }
+
+    ClipResult { result, result_sources, main: polygen, clipper }
+}
\ No newline at end of file
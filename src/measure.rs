@@ -0,0 +1,191 @@
+// 面积、周长、形心、包围盒、二阶矩等度量。
+// 回路按绘制时的约定闭合（末点与首点相同），末点缺省时也会自动补上闭合边。
+// 有向面积的符号与 is_path_anti_clockwise 一致：逆时针（外环）为正，顺时针（内环）为负，
+// 因此对整个 Polygen 求和即可自动减去洞的面积。
+
+use crate::{Path, Point, Polygen};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+}
+
+impl BoundingBox {
+    pub fn width(&self) -> f32 { self.max[0] - self.min[0] }
+
+    pub fn height(&self) -> f32 { self.max[1] - self.min[1] }
+
+    pub fn center(&self) -> Point {
+        Point::new([(self.min[0] + self.max[0]) / 2.0, (self.min[1] + self.max[1]) / 2.0])
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        let p = point.position;
+        p[0] >= self.min[0] && p[0] <= self.max[0] && p[1] >= self.min[1] && p[1] <= self.max[1]
+    }
+
+    // 边界相接也视为相交
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min[0] <= other.max[0] && other.min[0] <= self.max[0]
+            && self.min[1] <= other.max[1] && other.min[1] <= self.max[1]
+    }
+
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            min: [self.min[0].min(other.min[0]), self.min[1].min(other.min[1])],
+            max: [self.max[0].max(other.max[0]), self.max[1].max(other.max[1])],
+        }
+    }
+}
+
+// 面积二阶矩（惯性矩）：ixx = ∫y²dA，iyy = ∫x²dA，ixy = ∫xy dA，符号随有向面积
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SecondMoments {
+    pub ixx: f32,
+    pub iyy: f32,
+    pub ixy: f32,
+}
+
+// 依次给出回路的每条边（含首尾闭合边）
+fn edges(path: &Path) -> impl Iterator<Item = ([f64; 2], [f64; 2])> + '_ {
+    let n = path.len();
+    (0..n).map(move |i| {
+        let a = path[i].position;
+        let b = path[(i + 1) % n].position;
+        ([a[0] as f64, a[1] as f64], [b[0] as f64, b[1] as f64])
+    })
+}
+
+// 鞋带公式中每条边的叉积项，取与绘制约定一致的符号
+fn edge_cross(a: [f64; 2], b: [f64; 2]) -> f64 {
+    b[0] * a[1] - a[0] * b[1]
+}
+
+fn path_signed_area_f64(path: &Path) -> f64 {
+    edges(path).map(|(a, b)| edge_cross(a, b)).sum::<f64>() / 2.0
+}
+
+// 返回 (有向面积, 面积对x的一阶矩, 面积对y的一阶矩)
+fn path_first_moments(path: &Path) -> (f64, f64, f64) {
+    let (mut area, mut mx, mut my) = (0.0, 0.0, 0.0);
+    for (a, b) in edges(path) {
+        let c = edge_cross(a, b);
+        area += c / 2.0;
+        mx += (a[0] + b[0]) * c / 6.0;
+        my += (a[1] + b[1]) * c / 6.0;
+    }
+    (area, mx, my)
+}
+
+pub fn path_signed_area(path: &Path) -> f32 {
+    path_signed_area_f64(path) as f32
+}
+
+pub fn path_area(path: &Path) -> f32 {
+    path_signed_area(path).abs()
+}
+
+pub fn path_perimeter(path: &Path) -> f32 {
+    edges(path).map(|(a, b)| (b[0] - a[0]).hypot(b[1] - a[1])).sum::<f64>() as f32
+}
+
+// 面积为0的退化回路没有形心
+pub fn path_centroid(path: &Path) -> Option<Point> {
+    let (area, mx, my) = path_first_moments(path);
+    if area == 0.0 { return None; }
+    Some(Point::new([(mx / area) as f32, (my / area) as f32]))
+}
+
+pub fn path_bounding_box(path: &Path) -> Option<BoundingBox> {
+    let first = path.first()?.position;
+    let mut bbox = BoundingBox { min: first, max: first };
+    for point in path {
+        let p = point.position;
+        bbox.min = [bbox.min[0].min(p[0]), bbox.min[1].min(p[1])];
+        bbox.max = [bbox.max[0].max(p[0]), bbox.max[1].max(p[1])];
+    }
+    Some(bbox)
+}
+
+// 返回相对坐标原点的 (ixx, iyy, ixy)
+fn path_second_moments_f64(path: &Path) -> (f64, f64, f64) {
+    let (mut ixx, mut iyy, mut ixy) = (0.0, 0.0, 0.0);
+    for (a, b) in edges(path) {
+        let c = edge_cross(a, b);
+        ixx += c * (a[1] * a[1] + a[1] * b[1] + b[1] * b[1]) / 12.0;
+        iyy += c * (a[0] * a[0] + a[0] * b[0] + b[0] * b[0]) / 12.0;
+        ixy += c * (a[0] * b[1] + 2.0 * a[0] * a[1] + 2.0 * b[0] * b[1] + b[0] * a[1]) / 24.0;
+    }
+    (ixx, iyy, ixy)
+}
+
+// 相对坐标原点的二阶矩
+pub fn path_second_moments(path: &Path) -> SecondMoments {
+    let (ixx, iyy, ixy) = path_second_moments_f64(path);
+    SecondMoments { ixx: ixx as f32, iyy: iyy as f32, ixy: ixy as f32 }
+}
+
+pub fn polygen_signed_area(polygen: &Polygen) -> f32 {
+    polygen.iter().map(path_signed_area_f64).sum::<f64>() as f32
+}
+
+// 外环面积减去洞的面积
+pub fn polygen_area(polygen: &Polygen) -> f32 {
+    polygen_signed_area(polygen).abs()
+}
+
+// 所有回路（含洞）的周长之和
+pub fn polygen_perimeter(polygen: &Polygen) -> f32 {
+    polygen.iter().map(path_perimeter).sum()
+}
+
+pub fn polygen_centroid(polygen: &Polygen) -> Option<Point> {
+    let (mut area, mut mx, mut my) = (0.0, 0.0, 0.0);
+    for path in polygen {
+        let (a, x, y) = path_first_moments(path);
+        area += a;
+        mx += x;
+        my += y;
+    }
+    if area == 0.0 { return None; }
+    Some(Point::new([(mx / area) as f32, (my / area) as f32]))
+}
+
+pub fn polygen_bounding_box(polygen: &Polygen) -> Option<BoundingBox> {
+    polygen.iter().filter_map(path_bounding_box).reduce(|a, b| a.union(&b))
+}
+
+pub fn polygen_second_moments(polygen: &Polygen) -> SecondMoments {
+    let (mut ixx, mut iyy, mut ixy) = (0.0, 0.0, 0.0);
+    for path in polygen {
+        let m = path_second_moments_f64(path);
+        ixx += m.0;
+        iyy += m.1;
+        ixy += m.2;
+    }
+    SecondMoments { ixx: ixx as f32, iyy: iyy as f32, ixy: ixy as f32 }
+}
+
+// 相对形心的二阶矩（平行轴定理）。离原点较远时两项都很大而差很小，全程用 f64 计算
+pub fn polygen_central_moments(polygen: &Polygen) -> Option<SecondMoments> {
+    let (mut area, mut mx, mut my) = (0.0, 0.0, 0.0);
+    let (mut ixx, mut iyy, mut ixy) = (0.0, 0.0, 0.0);
+    for path in polygen {
+        let (a, x, y) = path_first_moments(path);
+        area += a;
+        mx += x;
+        my += y;
+        let m = path_second_moments_f64(path);
+        ixx += m.0;
+        iyy += m.1;
+        ixy += m.2;
+    }
+    if area == 0.0 { return None; }
+    let c = [mx / area, my / area];
+    Some(SecondMoments {
+        ixx: (ixx - area * c[1] * c[1]) as f32,
+        iyy: (iyy - area * c[0] * c[0]) as f32,
+        ixy: (ixy - area * c[0] * c[1]) as f32,
+    })
+}
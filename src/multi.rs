@@ -0,0 +1,63 @@
+// 多边形集合（每个多边形有自己的外环和洞）之间的布尔运算，结果也是多边形的集合。
+// 一次调用即可用一层多边形裁剪另一层，不需要调用方把所有回路合并进一个顶点表。
+
+use crate::boolean::{boolean, cascaded_union, BooleanOp};
+use crate::geom::{ring_vertices, ring_winding_number};
+use crate::measure::{path_signed_area, polygen_bounding_box};
+use crate::Polygen;
+
+// 把一组回路按嵌套关系分成若干个多边形：每个逆时针回路是一个外环，
+// 每个顺时针回路（洞）归入包含它的面积最小的外环；找不到外环的洞被丢弃
+pub fn split_polygens(rings: &Polygen) -> Vec<Polygen> {
+    split_indices(rings).into_iter()
+        .map(|polygen| polygen.into_iter().map(|i| rings[i].clone()).collect())
+        .collect()
+}
+
+// 同 split_polygens，但给出每个多边形由哪些回路（下标）组成，外环在前
+pub(crate) fn split_indices(rings: &Polygen) -> Vec<Vec<usize>> {
+    let areas: Vec<f32> = rings.iter().map(path_signed_area).collect();
+    let shells: Vec<usize> = (0..rings.len()).filter(|&i| areas[i] > 0.0).collect();
+    let mut result: Vec<Vec<usize>> = shells.iter().map(|&i| vec![i]).collect();
+    for hole in (0..rings.len()).filter(|&i| areas[i] < 0.0) {
+        let probe = match ring_vertices(&rings[hole]).first() {
+            Some(&p) => p,
+            None => continue,
+        };
+        let owner = (0..shells.len())
+            .filter(|&k| ring_winding_number(&rings[shells[k]], probe) != 0)
+            .min_by(|&a, &b| areas[shells[a]].total_cmp(&areas[shells[b]]));
+        if let Some(k) = owner { result[k].push(hole); }
+    }
+    result
+}
+
+// 并集：所有多边形一起级联合并。
+// 交集、差集：逐个处理主集合中的多边形，只取包围盒与之相交的裁剪多边形，合并后再运算，
+// 因此裁剪多边形之间有重叠时结果也不会重复
+pub fn boolean_sets(subjects: &[Polygen], clips: &[Polygen], op: BooleanOp) -> Vec<Polygen> {
+    if op == BooleanOp::Union {
+        let all: Vec<Polygen> = subjects.iter().chain(clips).cloned().collect();
+        return split_polygens(&cascaded_union(&all));
+    }
+    let clip_boxes: Vec<_> = clips.iter().map(polygen_bounding_box).collect();
+    let mut result = Vec::new();
+    for subject in subjects {
+        let bbox = match polygen_bounding_box(subject) {
+            Some(bbox) => bbox,
+            None => continue,
+        };
+        let nearby: Vec<Polygen> = clips.iter().zip(&clip_boxes)
+            .filter(|(_, b)| b.is_some_and(|b| b.intersects(&bbox)))
+            .map(|(c, _)| c.clone())
+            .collect();
+        let rings = if nearby.is_empty() {
+            if op == BooleanOp::Intersection { continue; }
+            subject.clone()
+        } else {
+            boolean(subject, &cascaded_union(&nearby), op)
+        };
+        result.extend(split_polygens(&rings));
+    }
+    result
+}
@@ -0,0 +1,67 @@
+// 排样（nesting）用的临界多边形。
+// 无拟合多边形（no-fit polygon）：固定件 A 不动，移动件 B 平移 t 后与 A 的内部重叠，当且仅当 t 落在 NFP 的内部；
+// t 在 NFP 边界上时两者恰好接触。NFP 等于 A 与 -B（B 关于原点的对称）的闵可夫斯基和。
+// 两个多边形都先做凸分解，每对凸块的闵可夫斯基和是它们顶点两两相加后的凸包，再求并集，
+// 因此非凸、带洞的情形也能处理（A 的洞大到能放下 B 时，NFP 里会有对应的洞）。
+// 内拟合多边形（inner-fit polygon）：B 平移 t 后完全落在板材 S 内的所有 t。
+// 这些凸包之间大量共边、共顶点，求并和求差用基于平面剖分的 boolean 和 cascaded_union。
+
+use crate::boolean::{boolean, cascaded_union, BooleanOp};
+use crate::decompose::convex_decomposition;
+use crate::geom::{close_ring, ring_vertices};
+use crate::hull::convex_hull;
+use crate::measure::{path_signed_area, polygen_bounding_box};
+use crate::{Path, Point, Polygen};
+
+// 闵可夫斯基和 {a + b | a ∈ A, b ∈ B}
+pub fn minkowski_sum(a: &Polygen, b: &Polygen) -> Polygen {
+    let (parts_a, parts_b) = (convex_decomposition(a, true), convex_decomposition(b, true));
+    let mut sums = Vec::with_capacity(parts_a.len() * parts_b.len());
+    for pa in &parts_a {
+        for pb in &parts_b {
+            let points: Vec<Point> = ring_vertices(pa).iter()
+                .flat_map(|p| ring_vertices(pb).iter().map(move |q| Point::new([p.position[0] + q.position[0], p.position[1] + q.position[1]])))
+                .collect();
+            let hull = convex_hull(&points);
+            if hull.len() >= 4 { sums.push(vec![hull]); }
+        }
+    }
+    cascaded_union(&sums)
+}
+
+// B 的参考点是它自己坐标系的原点，即 B 平移 t 后的顶点为 B 的顶点加上 t
+pub fn no_fit_polygon(fixed: &Polygen, moving: &Polygen) -> Polygen {
+    minkowski_sum(fixed, &reflect(moving))
+}
+
+// 板材的补集用一个比板材大一圈的外框减去板材表示：只有在 B 的包围盒落在板材的包围盒内的平移范围里，
+// B 越出板材才等价于与这个补集重叠，所以结果是这个范围减去补集与 B 的 NFP。B 放不下时为空
+pub fn inner_fit_polygon(sheet: &Polygen, part: &Polygen) -> Polygen {
+    let (s, b) = match (polygen_bounding_box(sheet), polygen_bounding_box(part)) {
+        (Some(s), Some(b)) => (s, b),
+        _ => return Vec::new(),
+    };
+    let (x0, y0) = (s.min[0] - b.min[0], s.min[1] - b.min[1]);
+    let (x1, y1) = (s.max[0] - b.max[0], s.max[1] - b.max[1]);
+    // 平移范围退化为线段或一点时结果没有面积，也算放不下
+    if x1 <= x0 || y1 <= y0 { return Vec::new(); }
+    let range = rectangle(x0, y0, x1, y1);
+    let margin = b.width().max(b.height()) + 1.0;
+    let frame = rectangle(s.min[0] - margin, s.min[1] - margin, s.max[0] + margin, s.max[1] + margin);
+    let complement = boolean(&vec![frame], sheet, BooleanOp::Difference);
+    boolean(&vec![range], &no_fit_polygon(&complement, part), BooleanOp::Difference)
+}
+
+// 关于原点对称，点对称不改变回路的方向
+fn reflect(polygen: &Polygen) -> Polygen {
+    polygen.iter()
+        .map(|path| path.iter().map(|p| Point::new([-p.position[0], -p.position[1]])).collect())
+        .collect()
+}
+
+// 逆时针的矩形
+fn rectangle(x0: f32, y0: f32, x1: f32, y1: f32) -> Path {
+    let mut ring = close_ring(vec![Point::new([x0, y0]), Point::new([x1, y0]), Point::new([x1, y1]), Point::new([x0, y1])]);
+    if path_signed_area(&ring) < 0.0 { ring.reverse(); }
+    ring
+}
@@ -0,0 +1,106 @@
+// 挖槽（pocketing）刀具路径：从刀具半径开始，每次多向内缩进一个步距（用直骨架内缩），直到区域缩没为止。
+// 多边形中的洞是岛屿，内缩时岛屿的边界向外扩，刀具绕开它们。
+// 每一层缩进的结果按外环分成若干区域，下一层的区域落在上一层的某个区域里，由此构成一棵嵌套的树。
+
+use crate::geom::{close_ring, ring_vertices, to_f64};
+use crate::measure::polygen_bounding_box;
+use crate::multi::split_polygens;
+use crate::polyline::clip_polyline;
+use crate::query::{contains, Location};
+use crate::skeleton::skeleton_insets;
+use crate::{Path, Polygen};
+
+pub struct PocketContour {
+    // 一个外环（逆时针）及其中的洞（顺时针，绕岛屿的路径）
+    pub polygen: Polygen,
+    // 到原多边形边界的距离
+    pub distance: f32,
+    // 下一层中落在这个区域里的各个区域
+    pub children: Vec<PocketContour>,
+}
+
+// 刀具中心的轨迹，距离依次为 tool_radius、tool_radius + stepover、……；返回第一层的各个区域
+pub fn pocket(polygen: &Polygen, tool_radius: f32, stepover: f32) -> Vec<PocketContour> {
+    let bbox = match polygen_bounding_box(polygen) {
+        Some(bbox) => bbox,
+        None => return Vec::new(),
+    };
+    if stepover.is_nan() || stepover <= 0.0 { return Vec::new(); }
+    let first = tool_radius.max(0.0);
+    // 缩进距离不会超过包围盒短边的一半
+    let limit = bbox.width().min(bbox.height()) / 2.0;
+    let count = ((limit - first) / stepover).floor().max(0.0) as usize + 1;
+    let distances: Vec<f32> = (0..count).map(|i| first + stepover * i as f32).collect();
+    let levels: Vec<(f32, Vec<Polygen>)> = distances.iter().zip(skeleton_insets(polygen, &distances))
+        .map(|(&d, inset)| (d, split_polygens(&inset)))
+        .take_while(|(_, regions)| !regions.is_empty())
+        .collect();
+
+    // 从最里层向外构造，每个区域挂到上一层中包含它的区域下
+    let mut below: Vec<PocketContour> = Vec::new();
+    for (distance, regions) in levels.into_iter().rev() {
+        let mut current: Vec<PocketContour> = regions.into_iter()
+            .map(|polygen| PocketContour { polygen, distance, children: Vec::new() })
+            .collect();
+        for child in below {
+            let parent = parent_of(&current, &child.polygen);
+            current[parent].children.push(child);
+        }
+        below = current;
+    }
+    below
+}
+
+// 外环有顶点不在其外部的区域；数值误差使所有顶点都落在外面时取包围盒中心最近的
+fn parent_of(candidates: &[PocketContour], polygen: &Polygen) -> usize {
+    let shell = ring_vertices(&polygen[0]);
+    if let Some(k) = candidates.iter().position(|c| shell.iter().any(|&p| contains(&c.polygen, p) != Location::Outside)) {
+        return k;
+    }
+    let center = |g: &Polygen| polygen_bounding_box(g).map_or([0.0, 0.0], |b| to_f64(b.center()));
+    let c = center(polygen);
+    (0..candidates.len()).min_by(|&i, &j| {
+        let (a, b) = (center(&candidates[i].polygen), center(&candidates[j].polygen));
+        (a[0] - c[0]).hypot(a[1] - c[1]).total_cmp(&(b[0] - c[0]).hypot(b[1] - c[1]))
+    }).unwrap()
+}
+
+// 把树中的回路由外向内连成尽量少的连续路径：每个回路从离上一个回路终点最近的顶点开始走一圈，
+// 两个回路之间的连线不离开第一层区域（刀具中心允许到达的范围）时接在同一条路径里，否则开始新的路径
+pub fn linked_toolpaths(contours: &[PocketContour]) -> Vec<Path> {
+    let mut result = Vec::new();
+    for root in contours {
+        let mut rings: Vec<&Path> = Vec::new();
+        collect_rings(root, &mut rings);
+        let mut current: Path = Vec::new();
+        for ring in rings {
+            let vertices = ring_vertices(ring);
+            if vertices.is_empty() { continue; }
+            let start = match current.last() {
+                None => 0,
+                Some(&end) => {
+                    let e = to_f64(end);
+                    let nearest = (0..vertices.len()).min_by(|&i, &j| {
+                        let (a, b) = (to_f64(vertices[i]), to_f64(vertices[j]));
+                        (a[0] - e[0]).hypot(a[1] - e[1]).total_cmp(&(b[0] - e[0]).hypot(b[1] - e[1]))
+                    }).unwrap();
+                    if !clip_polyline(&vec![end, vertices[nearest]], &root.polygen).outside.is_empty() {
+                        result.push(std::mem::take(&mut current));
+                    }
+                    nearest
+                }
+            };
+            let ring = close_ring(vertices[start..].iter().chain(&vertices[..start]).copied().collect());
+            let skip = (current.last() == Some(&ring[0])) as usize;
+            current.extend(&ring[skip..]);
+        }
+        if !current.is_empty() { result.push(current); }
+    }
+    result
+}
+
+// 先序遍历：本区域的各个回路，然后依次是各个子区域
+fn collect_rings<'a>(contour: &'a PocketContour, rings: &mut Vec<&'a Path>) {
+    rings.extend(&contour.polygen);
+    for child in &contour.children { collect_rings(child, rings); }
+}
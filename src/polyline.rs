@@ -0,0 +1,112 @@
+// 用多边形裁剪开放折线（道路、河流、切割线等）。
+// 折线上的位置用参数表示：整数部分是所在线段的下标，小数部分是在该线段上的比例（与顶点表中的 param1/param2 含义相同），
+// 例如 2.5 表示第2段（从0开始）的中点。与多边形边界重合的部分算作在多边形内。
+// 相距在 f32 舍入误差以内的切分点合并为一个，因此起点、终点落在边界上时不会产生极短的碎段，结果也与折线的方向无关。
+
+use crate::geom::{distance, orient, ring_vertices, to_f64, to_point};
+use crate::query::{contains, Location};
+use crate::{intersection, Line, Path, Point, Polygen};
+
+pub struct PolylinePiece {
+    pub path: Path,
+    pub start: f32,
+    pub end: f32,
+}
+
+pub struct PolylineClipResult {
+    pub inside: Vec<PolylinePiece>,
+    pub outside: Vec<PolylinePiece>,
+}
+
+pub fn clip_polyline(line: &Path, polygen: &Polygen) -> PolylineClipResult {
+    let mut result = PolylineClipResult { inside: Vec::new(), outside: Vec::new() };
+    let edges: Vec<Line> = polygen.iter().flat_map(|path| {
+        let vertices = ring_vertices(path);
+        (0..vertices.len()).map(move |i| [vertices[i], vertices[(i + 1) % vertices.len()]])
+    }).collect();
+
+    // 当前正在生成的一段：(是否在内部, 起点参数, 折线)
+    let mut current: Option<(bool, f32, Path)> = None;
+    for i in 0..line.len().saturating_sub(1) {
+        let segment = [line[i], line[i + 1]];
+        if segment[0] == segment[1] { continue; }
+        let tolerance = tolerance(&segment);
+        let mut params: Vec<f64> = Vec::new();
+        for edge in &edges {
+            if intersection(&segment, edge).is_some() {
+                // 交点参数用f64重新计算，减少切分点的误差；f32 判断相交而 f64 算出的参数略出界时截到端点
+                let (oa, ob) = (orient(edge[0], edge[1], segment[0]), orient(edge[0], edge[1], segment[1]));
+                if oa != ob { params.push((oa / (oa - ob)).clamp(0.0, 1.0)); }
+            }
+            // 共线重叠时 intersection 不给出交点，用边的端点切分；舍入后不严格共线的也算
+            for &p in edge {
+                let t = param_on_segment(&segment, p);
+                if (0.0..=1.0).contains(&t) && distance(p, lerp(&segment, t)) <= tolerance { params.push(t); }
+            }
+        }
+        params.sort_by(|a, b| a.total_cmp(b));
+        // 相距不到容差的切分点合并为一个，离端点不到容差的并入端点，避免产生长度只有几个 ulp 的碎段
+        let eps = tolerance / distance(segment[0], segment[1]);
+        let mut cuts = vec![0.0];
+        for s in params {
+            if s - cuts[cuts.len() - 1] > eps && 1.0 - s > eps { cuts.push(s); }
+        }
+        cuts.push(1.0);
+
+        for w in cuts.windows(2) {
+            let (s0, s1) = (w[0], w[1]);
+            let (p0, p1) = (lerp(&segment, s0), lerp(&segment, s1));
+            if p0 == p1 { continue; }
+            // 沿边界的一段直接算作内部，不依赖中点舍入后落在边的哪一侧
+            let inside = edges.iter().any(|edge| along_edge(p0, p1, edge, tolerance))
+                || contains(polygen, lerp(&segment, (s0 + s1) / 2.0)) != Location::Outside;
+            match &mut current {
+                Some((state, _, path)) if *state == inside => path.push(p1),
+                _ => {
+                    let start = (i as f64 + s0) as f32;
+                    if let Some(piece) = current.take() { finish(&mut result, piece, start); }
+                    current = Some((inside, start, vec![p0, p1]));
+                }
+            }
+        }
+    }
+    if let Some(piece) = current.take() {
+        finish(&mut result, piece, (line.len() - 1) as f32);
+    }
+    result
+}
+
+fn finish(result: &mut PolylineClipResult, (inside, start, path): (bool, f32, Path), end: f32) {
+    let piece = PolylinePiece { path, start, end };
+    if inside { result.inside.push(piece) } else { result.outside.push(piece) }
+}
+
+fn lerp(segment: &Line, s: f64) -> Point {
+    if s == 0.0 { return segment[0]; }
+    if s == 1.0 { return segment[1]; }
+    let (a, b) = (to_f64(segment[0]), to_f64(segment[1]));
+    to_point([a[0] + s * (b[0] - a[0]), a[1] + s * (b[1] - a[1])])
+}
+
+// 长度容差：线段端点坐标量级下 f32 的几个 ulp
+fn tolerance(segment: &Line) -> f64 {
+    let scale = segment.iter().flat_map(|p| p.position).fold(1.0f32, |m, v| m.max(v.abs()));
+    8.0 * f32::EPSILON as f64 * scale as f64
+}
+
+// p0、p1 到边的距离都不超过容差
+fn along_edge(p0: Point, p1: Point, edge: &Line, tolerance: f64) -> bool {
+    let near = |p: Point| {
+        let (a, b, p) = (to_f64(edge[0]), to_f64(edge[1]), to_f64(p));
+        let d = [b[0] - a[0], b[1] - a[1]];
+        let t = (((p[0] - a[0]) * d[0] + (p[1] - a[1]) * d[1]) / (d[0] * d[0] + d[1] * d[1])).clamp(0.0, 1.0);
+        (p[0] - a[0] - t * d[0]).hypot(p[1] - a[1] - t * d[1]) <= tolerance
+    };
+    edge[0] != edge[1] && near(p0) && near(p1)
+}
+
+fn param_on_segment(segment: &Line, p: Point) -> f64 {
+    let (a, b, p) = (to_f64(segment[0]), to_f64(segment[1]), to_f64(p));
+    let d = [b[0] - a[0], b[1] - a[1]];
+    ((p[0] - a[0]) * d[0] + (p[1] - a[1]) * d[1]) / (d[0] * d[0] + d[1] * d[1])
+}
@@ -0,0 +1,143 @@
+// 预处理过的多边形，用于对大量点做快速的点在多边形内判断。
+// 把包围盒划分成均匀网格，每个格子记录与之相交的边，以及格子内一个参考点的环绕数。
+// 查询时只需统计从参考点到查询点的线段与本格子内各边的交叉，平均每次查询只涉及常数条边。
+
+use crate::geom::{orient, point_on_segment, ring_vertices, to_f64};
+use crate::measure::{polygen_bounding_box, BoundingBox};
+use crate::query::Location;
+use crate::{Line, Point, Polygen};
+
+struct Cell {
+    edges: Vec<usize>,
+    reference: Point,
+    winding: i32,
+}
+
+pub struct PreparedPolygen {
+    edges: Vec<Line>,
+    bbox: Option<BoundingBox>,
+    columns: usize,
+    rows: usize,
+    cells: Vec<Cell>,
+}
+
+impl PreparedPolygen {
+    pub fn new(polygen: &Polygen) -> PreparedPolygen {
+        let edges: Vec<Line> = polygen.iter().flat_map(|path| {
+            let vertices = ring_vertices(path);
+            (0..vertices.len()).map(move |i| [vertices[i], vertices[(i + 1) % vertices.len()]])
+        }).filter(|e| e[0] != e[1]).collect();
+        let bbox = polygen_bounding_box(polygen);
+        let mut prepared = PreparedPolygen { edges, bbox, columns: 1, rows: 1, cells: Vec::new() };
+        if let Some(bbox) = bbox {
+            // 格子总数与边数同阶，按包围盒长宽比分配行列数
+            let n = prepared.edges.len().max(1) as f64;
+            let aspect = (bbox.width() as f64).max(f64::MIN_POSITIVE) / (bbox.height() as f64).max(f64::MIN_POSITIVE);
+            prepared.columns = ((n * aspect).sqrt().ceil() as usize).clamp(1, 1024);
+            prepared.rows = ((n / prepared.columns as f64).ceil() as usize).clamp(1, 1024);
+            prepared.build_cells();
+        }
+        prepared
+    }
+
+    pub fn contains(&self, point: Point) -> Location {
+        let bbox = match &self.bbox {
+            Some(bbox) if bbox.contains(point) => bbox,
+            _ => return Location::Outside,
+        };
+        let cell = &self.cells[self.cell_index(bbox, point)];
+        if cell.edges.iter().any(|&e| point_on_segment(point, &self.edges[e])) { return Location::OnBoundary; }
+        let winding = cell.winding + cell.edges.iter().map(|&e| crossing(&self.edges[e], cell.reference, point)).sum::<i32>();
+        if winding != 0 { Location::Inside } else { Location::Outside }
+    }
+
+    pub fn contains_all(&self, points: &[Point]) -> Vec<Location> {
+        points.iter().map(|&p| self.contains(p)).collect()
+    }
+
+    // 保留在多边形内部或边界上的点
+    pub fn filter_inside(&self, points: &[Point]) -> Vec<Point> {
+        points.iter().copied().filter(|&p| self.contains(p) != Location::Outside).collect()
+    }
+
+    fn cell_size(&self, bbox: &BoundingBox) -> [f64; 2] {
+        [bbox.width() as f64 / self.columns as f64, bbox.height() as f64 / self.rows as f64]
+    }
+
+    fn cell_coords(&self, bbox: &BoundingBox, p: [f64; 2]) -> (usize, usize) {
+        let size = self.cell_size(bbox);
+        let index = |v: f64, min: f32, size: f64, count: usize| {
+            if size <= 0.0 { 0 } else { (((v - min as f64) / size).floor().max(0.0) as usize).min(count - 1) }
+        };
+        (index(p[0], bbox.min[0], size[0], self.columns), index(p[1], bbox.min[1], size[1], self.rows))
+    }
+
+    fn cell_index(&self, bbox: &BoundingBox, point: Point) -> usize {
+        let (x, y) = self.cell_coords(bbox, to_f64(point));
+        y * self.columns + x
+    }
+
+    fn build_cells(&mut self) {
+        let bbox = self.bbox.unwrap();
+        let size = self.cell_size(&bbox);
+        let mut cell_edges = vec![Vec::new(); self.columns * self.rows];
+        for (i, edge) in self.edges.iter().enumerate() {
+            let (a, b) = (to_f64(edge[0]), to_f64(edge[1]));
+            let (x0, y0) = self.cell_coords(&bbox, [a[0].min(b[0]), a[1].min(b[1])]);
+            let (x1, y1) = self.cell_coords(&bbox, [a[0].max(b[0]), a[1].max(b[1])]);
+            for y in y0..=y1 {
+                for x in x0..=x1 { cell_edges[y * self.columns + x].push(i); }
+            }
+        }
+        for y in 0..self.rows {
+            // 过参考点的水平射线只会碰到这一行格子中的边
+            let mut row_edges: Vec<usize> = (0..self.columns).flat_map(|x| cell_edges[y * self.columns + x].iter().copied()).collect();
+            row_edges.sort_unstable();
+            row_edges.dedup();
+            for x in 0..self.columns {
+                let edges = std::mem::take(&mut cell_edges[y * self.columns + x]);
+                let reference = self.pick_reference(&bbox, size, x, y, &edges);
+                let winding = row_edges.iter().map(|&e| ray_crossing(&self.edges[e], reference)).sum();
+                self.cells.push(Cell { edges, reference, winding });
+            }
+        }
+    }
+
+    // 参考点取格子中心，恰好落在边上时换一个位置
+    fn pick_reference(&self, bbox: &BoundingBox, size: [f64; 2], x: usize, y: usize, edges: &[usize]) -> Point {
+        const OFFSETS: [[f64; 2]; 5] = [[0.5, 0.5], [0.382, 0.618], [0.618, 0.382], [0.271, 0.293], [0.707, 0.829]];
+        let candidates = OFFSETS.iter().map(|o| Point::new([
+            (bbox.min[0] as f64 + size[0] * (x as f64 + o[0])) as f32,
+            (bbox.min[1] as f64 + size[1] * (y as f64 + o[1])) as f32,
+        ]));
+        let mut fallback = None;
+        for p in candidates {
+            if !edges.iter().any(|&e| point_on_segment(p, &self.edges[e])) { return p; }
+            fallback.get_or_insert(p);
+        }
+        fallback.unwrap()
+    }
+}
+
+// 与 query::winding_number 相同的规则：统计边与从 p 出发向 x 正方向的射线的交叉
+fn ray_crossing(edge: &Line, p: Point) -> i32 {
+    let (a, b) = (edge[0], edge[1]);
+    let y = p.position[1];
+    if a.position[1] <= y {
+        if b.position[1] > y && orient(a, b, p) < 0.0 { return -1; }
+    } else if b.position[1] <= y && orient(a, b, p) > 0.0 {
+        return 1;
+    }
+    0
+}
+
+// 从 from 走到 to 穿过边 edge 时环绕数的变化。
+// 边的端点恰好落在直线 from-to 上时视为在其左侧（符号扰动），保证每次穿越只计一次
+fn crossing(edge: &Line, from: Point, to: Point) -> i32 {
+    let (a, b) = (edge[0], edge[1]);
+    if (orient(from, to, a) >= 0.0) == (orient(from, to, b) >= 0.0) { return 0; }
+    let (side_from, side_to) = (orient(a, b, from), orient(a, b, to));
+    if (side_from > 0.0) == (side_to > 0.0) { return 0; }
+    // 从边的右侧走到左侧（逆时针回路的内侧）时环绕数加一
+    if side_to > 0.0 { 1 } else { -1 }
+}
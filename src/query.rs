@@ -0,0 +1,28 @@
+// 点与多边形的位置关系查询。
+// 环绕数按 is_path_anti_clockwise 的约定计算：逆时针外环内部 +1，顺时针内环（洞）内部 -1，
+// 因此对整个 Polygen 求和后，洞内的环绕数为0，点在多边形内当且仅当环绕数不为0。
+
+use crate::geom::{point_on_segment, ring_vertices, ring_winding_number};
+use crate::{Point, Polygen};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Location { Inside, Outside, OnBoundary }
+
+// 点在任一回路的边上（包括洞的边）时为 OnBoundary
+pub fn contains(polygen: &Polygen, point: Point) -> Location {
+    if on_boundary(polygen, point) { return Location::OnBoundary; }
+    if winding_number(polygen, point) != 0 { Location::Inside } else { Location::Outside }
+}
+
+// 点在边上时环绕数没有良好定义，结果取决于具体的边，应先用 contains 判断
+pub fn winding_number(polygen: &Polygen, point: Point) -> i32 {
+    polygen.iter().map(|path| ring_winding_number(path, point)).sum()
+}
+
+fn on_boundary(polygen: &Polygen, point: Point) -> bool {
+    polygen.iter().any(|path| {
+        let vertices = ring_vertices(path);
+        let n = vertices.len();
+        (0..n).any(|i| point_on_segment(point, &[vertices[i], vertices[(i + 1) % n]]))
+    })
+}
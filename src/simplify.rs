@@ -0,0 +1,232 @@
+// 多边形化简：Douglas–Peucker 与 Visvalingam–Whyatt 两种算法。
+// 化简只删除顶点，不移动顶点；每个回路至少保留3个顶点。
+// preserve_topology 为 true 时，会把引起问题的被删顶点逐个恢复，直到：
+// 化简后没有新的自相交/回路间相交，回路方向不翻转、面积不塌缩为0，洞仍留在原来的外环内。
+
+use crate::geom::{close_ring, distance, orient, point_segment_distance, ring_vertices, ring_winding_number, segments_intersect};
+use crate::measure::path_signed_area;
+use crate::{Path, Point, Polygen};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SimplifyAlgorithm {
+    // tolerance 为被删顶点到化简后线段的最大距离
+    DouglasPeucker,
+    // tolerance 为被删顶点与相邻顶点构成三角形的最小面积
+    VisvalingamWhyatt,
+}
+
+// 化简单个回路，不做拓扑检查
+pub fn simplify_path(path: &Path, algorithm: SimplifyAlgorithm, tolerance: f32) -> Path {
+    let vertices = ring_vertices(path);
+    let kept = simplify_mask(vertices, algorithm, tolerance as f64);
+    apply_mask(vertices, &kept)
+}
+
+pub fn simplify(polygen: &Polygen, algorithm: SimplifyAlgorithm, tolerance: f32, preserve_topology: bool) -> Polygen {
+    let rings: Vec<&[Point]> = polygen.iter().map(ring_vertices).collect();
+    let mut masks: Vec<Vec<bool>> = rings.iter().map(|ring| simplify_mask(ring, algorithm, tolerance as f64)).collect();
+    if preserve_topology {
+        let shells: Vec<Option<usize>> = (0..polygen.len()).map(|i| enclosing_shell(polygen, i)).collect();
+        // 每轮至少恢复一个顶点，顶点全部恢复后就是合法的原多边形，因此一定会终止
+        while repair_once(polygen, &rings, &mut masks, &shells) {}
+    }
+    rings.iter().zip(masks.iter()).map(|(ring, kept)| apply_mask(ring, kept)).collect()
+}
+
+fn apply_mask(vertices: &[Point], kept: &[bool]) -> Path {
+    close_ring(vertices.iter().zip(kept).filter(|(_, &k)| k).map(|(p, _)| *p).collect())
+}
+
+fn simplify_mask(vertices: &[Point], algorithm: SimplifyAlgorithm, tolerance: f64) -> Vec<bool> {
+    let n = vertices.len();
+    if n <= 3 { return vec![true; n]; }
+    match algorithm {
+        SimplifyAlgorithm::DouglasPeucker => douglas_peucker(vertices, tolerance),
+        SimplifyAlgorithm::VisvalingamWhyatt => visvalingam_whyatt(vertices, tolerance),
+    }
+}
+
+// 在 (from, to) 之间（按环形顺序，不含端点）找离线段 from-to 最远的顶点
+fn farthest_between(vertices: &[Point], from: usize, to: usize) -> Option<(usize, f64)> {
+    let n = vertices.len();
+    let line = [vertices[from], vertices[to]];
+    let mut best: Option<(usize, f64)> = None;
+    let mut i = (from + 1) % n;
+    while i != to {
+        let d = point_segment_distance(vertices[i], &line);
+        if best.is_none_or(|(_, bd)| d > bd) { best = Some((i, d)); }
+        i = (i + 1) % n;
+    }
+    best
+}
+
+fn douglas_peucker(vertices: &[Point], tolerance: f64) -> Vec<bool> {
+    let n = vertices.len();
+    let mut kept = vec![false; n];
+    // 闭合回路先取首点和离它最远的点作为两个锚点，再分别化简两半
+    let far = (1..n).max_by(|&a, &b| {
+        distance(vertices[0], vertices[a]).total_cmp(&distance(vertices[0], vertices[b]))
+    }).unwrap();
+    kept[0] = true;
+    kept[far] = true;
+    let mut stack = vec![(0, far), (far, 0)];
+    while let Some((from, to)) = stack.pop() {
+        if let Some((i, d)) = farthest_between(vertices, from, to) {
+            if d > tolerance {
+                kept[i] = true;
+                stack.push((from, i));
+                stack.push((i, to));
+            }
+        }
+    }
+    ensure_three(vertices, &mut kept);
+    kept
+}
+
+fn visvalingam_whyatt(vertices: &[Point], tolerance: f64) -> Vec<bool> {
+    let n = vertices.len();
+    let mut kept = vec![true; n];
+    let mut prev: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+    let area = |prev: &[usize], next: &[usize], i: usize| {
+        orient(vertices[prev[i]], vertices[i], vertices[next[i]]).abs() / 2.0
+    };
+    let mut areas: Vec<f64> = (0..n).map(|i| area(&prev, &next, i)).collect();
+    let mut remaining = n;
+    while remaining > 3 {
+        let (i, &a) = areas.iter().enumerate().filter(|(i, _)| kept[*i])
+            .min_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+        if a >= tolerance { break; }
+        kept[i] = false;
+        remaining -= 1;
+        let (p, q) = (prev[i], next[i]);
+        next[p] = q;
+        prev[q] = p;
+        // 保证面积单调不减，避免先删后补出现的抖动
+        areas[p] = area(&prev, &next, p).max(a);
+        areas[q] = area(&prev, &next, q).max(a);
+    }
+    kept
+}
+
+// 锚点共线时 DP 可能只剩2个点，补上最远的点
+fn ensure_three(vertices: &[Point], kept: &mut [bool]) {
+    while kept.iter().filter(|&&k| k).count() < 3 {
+        let idx: Vec<usize> = (0..kept.len()).filter(|&i| kept[i]).collect();
+        let mut best: Option<(usize, f64)> = None;
+        for (k, &from) in idx.iter().enumerate() {
+            let to = idx[(k + 1) % idx.len()];
+            if let Some((i, d)) = farthest_between(vertices, from, to) {
+                if best.is_none_or(|(_, bd)| d > bd) { best = Some((i, d)); }
+            }
+        }
+        match best {
+            Some((i, _)) => kept[i] = true,
+            None => break,
+        }
+    }
+}
+
+// 洞所属的外环：原多边形中包含该洞的面积最小的逆时针回路
+fn enclosing_shell(polygen: &Polygen, hole: usize) -> Option<usize> {
+    if path_signed_area(&polygen[hole]) >= 0.0 { return None; }
+    let probe = polygen[hole][0];
+    (0..polygen.len())
+        .filter(|&i| path_signed_area(&polygen[i]) > 0.0 && ring_winding_number(&polygen[i], probe) != 0)
+        .min_by(|&a, &b| path_signed_area(&polygen[a]).total_cmp(&path_signed_area(&polygen[b])))
+}
+
+// 化简后的一条边：所在回路，起止顶点在原回路中的下标
+struct Edge {
+    ring: usize,
+    from: usize,
+    to: usize,
+    line: [Point; 2],
+}
+
+fn simplified_edges(rings: &[&[Point]], masks: &[Vec<bool>]) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    for (r, kept) in masks.iter().enumerate() {
+        let idx: Vec<usize> = (0..kept.len()).filter(|&i| kept[i]).collect();
+        for (k, &from) in idx.iter().enumerate() {
+            let to = idx[(k + 1) % idx.len()];
+            edges.push(Edge { ring: r, from, to, line: [rings[r][from], rings[r][to]] });
+        }
+    }
+    edges
+}
+
+// 恢复化简边 from-to 之间偏离最大的顶点；没有被删顶点时返回 false
+fn restore(rings: &[&[Point]], masks: &mut [Vec<bool>], ring: usize, from: usize, to: usize) -> bool {
+    match farthest_between(rings[ring], from, to) {
+        Some((i, _)) => {
+            masks[ring][i] = true;
+            true
+        }
+        None => false,
+    }
+}
+
+// 检查一轮并恢复有问题的顶点，有改动时返回 true
+fn repair_once(polygen: &Polygen, rings: &[&[Point]], masks: &mut [Vec<bool>], shells: &[Option<usize>]) -> bool {
+    let mut changed = false;
+
+    // 回路方向翻转或面积塌缩
+    for r in 0..rings.len() {
+        let original = path_signed_area(&polygen[r]);
+        let simplified = path_signed_area(&apply_mask(rings[r], &masks[r]));
+        if original != 0.0 && (simplified == 0.0 || simplified.signum() != original.signum()) {
+            let edges = simplified_edges(&rings[r..r + 1], &masks[r..r + 1]);
+            for e in edges {
+                changed |= restore(rings, masks, r, e.from, e.to);
+            }
+        }
+    }
+    if changed { return true; }
+
+    // 边与边相交（相邻边共用端点不算）
+    let edges = simplified_edges(rings, masks);
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    let min_x = |e: &Edge| e.line[0].position[0].min(e.line[1].position[0]);
+    let max_x = |e: &Edge| e.line[0].position[0].max(e.line[1].position[0]);
+    order.sort_by(|&a, &b| min_x(&edges[a]).total_cmp(&min_x(&edges[b])));
+    for (k, &i) in order.iter().enumerate() {
+        for &j in &order[k + 1..] {
+            if min_x(&edges[j]) > max_x(&edges[i]) { break; }
+            let (a, b) = (&edges[i], &edges[j]);
+            if a.ring == b.ring && (a.to == b.from || b.to == a.from) {
+                // 相邻边只需检查是否共线重叠
+                let shared = if a.to == b.from { (a.line[0], a.line[1], b.line[1]) } else { (b.line[0], b.line[1], a.line[1]) };
+                if !(orient(shared.0, shared.1, shared.2) == 0.0 && backtracks(shared.0, shared.1, shared.2)) { continue; }
+            } else if !segments_intersect(&a.line, &b.line) {
+                continue;
+            }
+            changed |= restore(rings, masks, a.ring, a.from, a.to);
+            changed |= restore(rings, masks, b.ring, b.from, b.to);
+        }
+    }
+    if changed { return true; }
+
+    // 洞跑到了外环外面：恢复外环上离洞最近的那条化简边，外环已无可恢复的顶点时再恢复洞
+    for (hole, shell) in shells.iter().enumerate() {
+        if let Some(shell) = *shell {
+            let probe = rings[hole][masks[hole].iter().position(|&k| k).unwrap()];
+            if ring_winding_number(&apply_mask(rings[shell], &masks[shell]), probe) == 0 {
+                let mut shell_edges = simplified_edges(&rings[shell..shell + 1], &masks[shell..shell + 1]);
+                shell_edges.sort_by(|a, b| point_segment_distance(probe, &a.line).total_cmp(&point_segment_distance(probe, &b.line)));
+                let restored = shell_edges.iter().any(|e| restore(rings, masks, shell, e.from, e.to));
+                changed |= restored || {
+                    let hole_edges = simplified_edges(&rings[hole..hole + 1], &masks[hole..hole + 1]);
+                    hole_edges.iter().any(|e| restore(rings, masks, hole, e.from, e.to))
+                };
+            }
+        }
+    }
+    changed
+}
+
+// 共线的 a->b->c 是否折返（形成尖刺）
+fn backtracks(a: Point, b: Point, c: Point) -> bool {
+    let (a, b, c) = (a.position, b.position, c.position);
+    (b[0] - a[0]) * (c[0] - b[0]) + (b[1] - a[1]) * (c[1] - b[1]) < 0.0
+}
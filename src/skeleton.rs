@@ -0,0 +1,488 @@
+// 直骨架（straight skeleton）及基于直骨架的内缩/外扩。
+// 把每条边以单位速度向内平移（波前），顶点沿相邻两边的角平分线移动，模拟波前的变化：
+// 边事件——一条波前边缩短为0，两端顶点合并；分裂事件——凹顶点撞上另一条波前边，把波前分成两个
+// （撞上的是另一个回路的边时两个波前合并为一个）。顶点的轨迹就是骨架的边，每条输入边扫过的区域是它的面。
+// 每个事件都在当前整个波前上找最早的碰撞，复杂度约为 O(n^3)，适合几百个顶点以内的多边形。
+// 输入应为合法的多边形（外环逆时针、洞顺时针），重复点会被去掉。
+
+use crate::geom::{close_ring, ring_vertices, to_f64, to_point};
+use crate::measure::{path_signed_area, polygen_bounding_box};
+use crate::{EdgeRef, Path, Point, Polygen};
+
+// 骨架节点，height 是它到多边形边界的距离（即波前到达这里的时间），原多边形的顶点高度为0
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SkeletonNode {
+    pub point: Point,
+    pub height: f32,
+}
+
+// 骨架的边（节点下标），edges 是它两侧的面对应的输入边
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SkeletonArc {
+    pub from: usize,
+    pub to: usize,
+    pub edges: [EdgeRef; 2],
+}
+
+// 一条输入边扫过的区域，逆时针闭合回路，从这条边的起点、终点开始
+pub struct SkeletonFace {
+    pub edge: EdgeRef,
+    pub path: Path,
+}
+
+pub struct StraightSkeleton {
+    pub nodes: Vec<SkeletonNode>,
+    pub arcs: Vec<SkeletonArc>,
+    pub faces: Vec<SkeletonFace>,
+}
+
+pub fn straight_skeleton(polygen: &Polygen) -> StraightSkeleton {
+    let mut wavefront = Wavefront::new(polygen, None);
+    wavefront.run(f64::INFINITY);
+    let nodes = wavefront.nodes.iter()
+        .map(|&(p, t)| SkeletonNode { point: to_point(p), height: t as f32 })
+        .collect();
+    let arcs = wavefront.arcs.iter()
+        .map(|&(from, to, [l1, l2])| SkeletonArc { from, to, edges: [wavefront.lines[l1].id, wavefront.lines[l2].id] })
+        .collect();
+    let faces = (0..wavefront.lines.len()).map(|k| wavefront.face(k)).collect();
+    StraightSkeleton { nodes, arcs, faces }
+}
+
+// 向内缩进 distance，结果是若干外环和洞（与输入相同的格式），缩没了时为空
+pub fn skeleton_inset(polygen: &Polygen, distance: f32) -> Polygen {
+    skeleton_insets(polygen, &[distance]).pop().unwrap()
+}
+
+// 一次模拟得到多个缩进距离的结果，与 distances 一一对应
+pub fn skeleton_insets(polygen: &Polygen, distances: &[f32]) -> Vec<Polygen> {
+    let mut order: Vec<usize> = (0..distances.len()).collect();
+    order.sort_by(|&a, &b| distances[a].total_cmp(&distances[b]));
+    let mut wavefront = Wavefront::new(polygen, None);
+    let mut result = vec![Vec::new(); distances.len()];
+    for i in order {
+        let d = distances[i].max(0.0) as f64;
+        wavefront.run(d);
+        result[i] = wavefront.snapshot(d);
+    }
+    result
+}
+
+// 向外扩张 distance：把回路反向后放进一个足够大的外框里，对这个“补集”做内缩，再去掉外框
+pub fn skeleton_outset(polygen: &Polygen, distance: f32) -> Polygen {
+    let d = distance.max(0.0) as f64;
+    let mut wavefront = Wavefront::new(polygen, Some(d));
+    wavefront.run(d);
+    wavefront.snapshot(d).into_iter()
+        .map(|mut ring| {
+            ring.reverse();
+            ring
+        })
+        .collect()
+}
+
+// 平移的边所在的直线：t 时刻为 n·(x - a) = t
+struct OffsetLine {
+    a: [f64; 2],
+    // 单位方向和指向内部（左侧）的单位法向
+    d: [f64; 2],
+    n: [f64; 2],
+    id: EdgeRef,
+    // 这条边起点、终点处的骨架节点
+    start_node: usize,
+    end_node: usize,
+    // 外扩时添加的外框
+    frame: bool,
+}
+
+// 波前顶点：t 时刻位于 start + vel * (t - t0)，在直线 left（前一条边）和 right（后一条边）的交点上
+#[derive(Copy, Clone)]
+struct WaveVertex {
+    start: [f64; 2],
+    t0: f64,
+    vel: [f64; 2],
+    left: usize,
+    right: usize,
+    prev: usize,
+    next: usize,
+    // 出发时所在的骨架节点
+    node: usize,
+    alive: bool,
+}
+
+enum Event {
+    // 顶点 u 与 u.next 之间的边缩短为0
+    Edge(usize),
+    // 凹顶点 v 撞上从顶点 a 出发的波前边
+    Split(usize, usize),
+}
+
+// (输入回路下标, 去重后的顶点, 每条边对应的输入边, 是否为外框)
+type WaveRing = (usize, Vec<[f64; 2]>, Vec<usize>, bool);
+
+struct Wavefront {
+    lines: Vec<OffsetLine>,
+    vertices: Vec<WaveVertex>,
+    nodes: Vec<([f64; 2], f64)>,
+    arcs: Vec<(usize, usize, [usize; 2])>,
+    now: f64,
+    eps: f64,
+}
+
+fn dot(a: [f64; 2], b: [f64; 2]) -> f64 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn sub(a: [f64; 2], b: [f64; 2]) -> [f64; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+impl Wavefront {
+    // outset 为外扩距离时构造反向的回路和外框
+    fn new(polygen: &Polygen, outset: Option<f64>) -> Wavefront {
+        let mut rings: Vec<WaveRing> = Vec::new();
+        for (ring, path) in polygen.iter().enumerate() {
+            let input = ring_vertices(path);
+            let mut points: Vec<[f64; 2]> = Vec::new();
+            let mut indices = Vec::new();
+            for (i, &p) in input.iter().enumerate() {
+                let p = to_f64(p);
+                if points.last() != Some(&p) {
+                    points.push(p);
+                    indices.push(i);
+                }
+            }
+            while points.len() > 1 && points[0] == points[points.len() - 1] {
+                points.pop();
+                indices.pop();
+            }
+            if points.len() < 3 { continue; }
+            // 边 k 对应输入中以下一个保留的顶点为终点的边
+            let n = input.len();
+            let edges: Vec<usize> = (0..points.len()).map(|k| (indices[(k + 1) % indices.len()] + n - 1) % n).collect();
+            if outset.is_some() { points.reverse(); }
+            rings.push((ring, points, edges, false));
+        }
+        let mut scale: f64 = 1.0;
+        if let Some(distance) = outset {
+            if let Some(bbox) = polygen_bounding_box(polygen) {
+                // 外框到多边形的距离大于扩张距离的两倍，两者的波前在扩张结束前不会相遇
+                let m = (bbox.width() as f64).hypot(bbox.height() as f64) + 2.0 * distance + 1.0;
+                let (x0, y0) = (bbox.min[0] as f64 - m, bbox.min[1] as f64 - m);
+                let (x1, y1) = (bbox.max[0] as f64 + m, bbox.max[1] as f64 + m);
+                rings.push((usize::MAX, vec![[x0, y0], [x0, y1], [x1, y1], [x1, y0]], vec![0, 1, 2, 3], true));
+            }
+        }
+        for (_, points, _, _) in &rings {
+            for p in points { scale = scale.max(p[0].abs()).max(p[1].abs()); }
+        }
+
+        let mut wavefront = Wavefront { lines: Vec::new(), vertices: Vec::new(), nodes: Vec::new(), arcs: Vec::new(), now: 0.0, eps: scale * 1e-9 };
+        for (ring, points, edges, frame) in rings {
+            let m = points.len();
+            let first_line = wavefront.lines.len();
+            let first_vertex = wavefront.vertices.len();
+            let first_node = wavefront.nodes.len();
+            for p in &points { wavefront.nodes.push((*p, 0.0)); }
+            for k in 0..m {
+                let (a, b) = (points[k], points[(k + 1) % m]);
+                let len = (b[0] - a[0]).hypot(b[1] - a[1]);
+                let d = [(b[0] - a[0]) / len, (b[1] - a[1]) / len];
+                // 外扩时回路被反向，边下标按原来的方向计
+                let edge = if outset.is_some() && !frame { edges[(2 * m - 2 - k) % m] } else { edges[k] };
+                wavefront.lines.push(OffsetLine {
+                    a,
+                    d,
+                    n: [d[1], -d[0]],
+                    id: EdgeRef { ring, edge },
+                    start_node: first_node + k,
+                    end_node: first_node + (k + 1) % m,
+                    frame,
+                });
+            }
+            for (k, &start) in points.iter().enumerate() {
+                let left = first_line + (k + m - 1) % m;
+                let right = first_line + k;
+                let vel = wavefront.velocity(left, right);
+                wavefront.vertices.push(WaveVertex {
+                    start,
+                    t0: 0.0,
+                    vel,
+                    left,
+                    right,
+                    prev: first_vertex + (k + m - 1) % m,
+                    next: first_vertex + (k + 1) % m,
+                    node: first_node + k,
+                    alive: true,
+                });
+            }
+        }
+        wavefront
+    }
+
+    // 两条平移直线交点的移动速度：n1·v = 1 且 n2·v = 1
+    fn velocity(&self, l1: usize, l2: usize) -> [f64; 2] {
+        let (n1, n2) = (self.lines[l1].n, self.lines[l2].n);
+        let det = n1[0] * n2[1] - n1[1] * n2[0];
+        if det.abs() < 1e-12 {
+            // 共线同向时沿法向移动；方向相反时两边重合，这一段波前即将消失
+            return if dot(n1, n2) > 0.0 { n1 } else { [0.0, 0.0] };
+        }
+        [(n2[1] - n1[1]) / det, (n1[0] - n2[0]) / det]
+    }
+
+    fn position(&self, v: usize, t: f64) -> [f64; 2] {
+        let v = &self.vertices[v];
+        [v.start[0] + v.vel[0] * (t - v.t0), v.start[1] + v.vel[1] * (t - v.t0)]
+    }
+
+    // 前一条边转到后一条边是向右转（凹顶点）
+    fn is_reflex(&self, v: usize) -> bool {
+        let (d1, d2) = (self.lines[self.vertices[v].left].d, self.lines[self.vertices[v].right].d);
+        d1[1] * d2[0] - d1[0] * d2[1] < -1e-12
+    }
+
+    // 同时发生的多个事件可能在同一位置，复用已有的节点
+    fn add_node(&mut self, p: [f64; 2], t: f64) -> usize {
+        let tolerance = self.eps * 1e3;
+        let found = self.nodes.iter().rposition(|&(q, h)| (h - t).abs() <= tolerance && (p[0] - q[0]).hypot(p[1] - q[1]) <= tolerance);
+        if let Some(node) = found { return node; }
+        self.nodes.push((p, t));
+        self.nodes.len() - 1
+    }
+
+    // 顶点 v 到达节点 node，记录它的轨迹并移出波前
+    fn finish(&mut self, v: usize, node: usize) {
+        let vertex = self.vertices[v];
+        if vertex.node != node { self.arcs.push((vertex.node, node, [vertex.left, vertex.right])); }
+        self.vertices[v].alive = false;
+    }
+
+    fn add_vertex(&mut self, start: [f64; 2], left: usize, right: usize, prev: usize, next: usize, node: usize) -> usize {
+        let vel = self.velocity(left, right);
+        self.vertices.push(WaveVertex { start, t0: self.now, vel, left, right, prev, next, node, alive: true });
+        let v = self.vertices.len() - 1;
+        self.vertices[prev].next = v;
+        self.vertices[next].prev = v;
+        v
+    }
+
+    fn cycle(&self, v: usize) -> Vec<usize> {
+        let mut result = vec![v];
+        let mut cur = self.vertices[v].next;
+        while cur != v && result.len() <= self.vertices.len() {
+            result.push(cur);
+            cur = self.vertices[cur].next;
+        }
+        result
+    }
+
+    // 处理 stop 之前的所有事件。设开始时波前有 n 个顶点，其中 r 个凹顶点：
+    // 边事件使顶点数减1，合并出的顶点内角为原来两个内角之和减去180°，两个都凸时仍是凸的，凹顶点数不增加；
+    // 分裂事件使顶点数加1，只发生在凹顶点上，分出的两个顶点内角之和为原来的内角减去180°，都是凸的，凹顶点数减1。
+    // 所以分裂事件不超过 r 次，边事件不超过 n + r 次，事件总数小于 3n
+    fn run(&mut self, stop: f64) {
+        let limit = 3 * self.vertices.len();
+        for _ in 0..limit {
+            let (t, event) = match self.next_event() {
+                Some(e) => e,
+                None => return,
+            };
+            if t > stop { return; }
+            self.now = t.max(self.now);
+            match event {
+                Event::Edge(u) => self.edge_event(u),
+                Event::Split(v, a) => self.split_event(v, a),
+            }
+        }
+        debug_assert!(self.next_event().is_none_or(|(t, _)| t > stop), "事件数超过了上界 {}", limit);
+    }
+
+    fn next_event(&self) -> Option<(f64, Event)> {
+        let alive: Vec<usize> = (0..self.vertices.len()).filter(|&v| self.vertices[v].alive).collect();
+        let mut best_edge: Option<(f64, usize)> = None;
+        for &u in &alive {
+            let w = self.vertices[u].next;
+            let d = self.lines[self.vertices[u].right].d;
+            let length = dot(d, sub(self.position(w, self.now), self.position(u, self.now)));
+            let rate = dot(d, sub(self.vertices[u].vel, self.vertices[w].vel));
+            let t = if length <= self.eps {
+                self.now
+            } else if rate > 1e-12 {
+                self.now + length / rate
+            } else {
+                continue;
+            };
+            if best_edge.is_none_or(|(bt, _)| t < bt) { best_edge = Some((t, u)); }
+        }
+        let mut best_split: Option<(f64, usize, usize)> = None;
+        for &v in alive.iter().filter(|&&v| self.is_reflex(v)) {
+            let vertex = &self.vertices[v];
+            let pv = self.position(v, self.now);
+            for &a in &alive {
+                let b = self.vertices[a].next;
+                let k = self.vertices[a].right;
+                if a == v || b == v || k == vertex.left || k == vertex.right { continue; }
+                let line = &self.lines[k];
+                let gap = dot(line.n, sub(pv, line.a)) - self.now;
+                let approach = 1.0 - dot(line.n, vertex.vel);
+                if gap < -self.eps || approach <= 1e-12 { continue; }
+                let t = self.now + gap.max(0.0) / approach;
+                if best_split.is_some_and(|(bt, _, _)| t >= bt) { continue; }
+                let p = self.position(v, t);
+                let s = dot(line.d, p);
+                if s < dot(line.d, self.position(a, t)) - self.eps || s > dot(line.d, self.position(b, t)) + self.eps { continue; }
+                best_split = Some((t, v, a));
+            }
+        }
+        // 同时发生时先处理边事件
+        match (best_edge, best_split) {
+            (Some((te, u)), Some((ts, v, a))) => {
+                if ts < te - self.eps { Some((ts, Event::Split(v, a))) } else { Some((te, Event::Edge(u))) }
+            }
+            (Some((te, u)), None) => Some((te, Event::Edge(u))),
+            (None, Some((ts, v, a))) => Some((ts, Event::Split(v, a))),
+            (None, None) => None,
+        }
+    }
+
+    fn edge_event(&mut self, u: usize) {
+        let w = self.vertices[u].next;
+        let p = self.position(u, self.now);
+        let node = self.add_node(p, self.now);
+        let cycle = self.cycle(u);
+        let tolerance = self.eps * 1e3;
+        let meets = |v: usize| {
+            let q = self.position(v, self.now);
+            (p[0] - q[0]).hypot(p[1] - q[1]) <= tolerance
+        };
+        if cycle.len() <= 3 && cycle.iter().all(|&v| meets(v)) {
+            // 三角形缩为一点
+            for v in cycle { self.finish(v, node); }
+            return;
+        }
+        let (left, prev) = (self.vertices[u].left, self.vertices[u].prev);
+        let (right, next) = (self.vertices[w].right, self.vertices[w].next);
+        self.finish(u, node);
+        self.finish(w, node);
+        let x = self.add_vertex(p, left, right, prev, next, node);
+        self.collapse_flat(x);
+    }
+
+    fn split_event(&mut self, v: usize, a: usize) {
+        let b = self.vertices[a].next;
+        let k = self.vertices[a].right;
+        let p = self.position(v, self.now);
+        let node = self.add_node(p, self.now);
+        let vertex = self.vertices[v];
+        self.finish(v, node);
+        let x1 = self.add_vertex(p, vertex.left, k, vertex.prev, b, node);
+        let x2 = self.add_vertex(p, k, vertex.right, a, vertex.next, node);
+        self.collapse_flat(x1);
+        self.collapse_flat(x2);
+    }
+
+    // 面积为0的波前（所有顶点共线，例如两条平行边相遇）整个消失：
+    // 按顶点在这条线段上的位置排序，相邻位置之间是一条骨架边，两侧是沿正反两个方向覆盖它的波前边
+    fn collapse_flat(&mut self, x: usize) {
+        if !self.vertices[x].alive { return; }
+        let cycle = self.cycle(x);
+        let points: Vec<[f64; 2]> = cycle.iter().map(|&v| self.position(v, self.now)).collect();
+        let m = points.len();
+        let tolerance = self.eps * 1e3;
+        if m > 2 {
+            let mut area = 0.0;
+            let mut perimeter = 0.0;
+            for i in 0..m {
+                let (p, q) = (points[i], points[(i + 1) % m]);
+                area += p[0] * q[1] - q[0] * p[1];
+                perimeter += (q[0] - p[0]).hypot(q[1] - p[1]);
+            }
+            if area.abs() / 2.0 > tolerance * perimeter { return; }
+        }
+        // 以离第一个顶点最远的顶点确定线段方向
+        let far = (0..m).max_by(|&i, &j| {
+            let di = sub(points[i], points[0]);
+            let dj = sub(points[j], points[0]);
+            dot(di, di).total_cmp(&dot(dj, dj))
+        }).unwrap();
+        let span = sub(points[far], points[0]);
+        let length = dot(span, span).sqrt();
+        let dir = if length > tolerance { [span[0] / length, span[1] / length] } else { [1.0, 0.0] };
+        // 面积为0但不共线（例如树状的波前）时仍按一般的事件处理
+        if points.iter().any(|&p| (dir[0] * (p[1] - points[0][1]) - dir[1] * (p[0] - points[0][0])).abs() > tolerance) { return; }
+        let s: Vec<f64> = points.iter().map(|&p| dot(dir, p)).collect();
+        let mut order: Vec<usize> = (0..m).collect();
+        order.sort_by(|&i, &j| s[i].total_cmp(&s[j]));
+        // 相距在容差内的顶点共用一个节点
+        let mut stops: Vec<(f64, usize)> = Vec::new();
+        let mut stop_of = vec![0; m];
+        for &i in &order {
+            if stops.last().is_none_or(|&(last, _)| s[i] - last > tolerance) {
+                let node = self.add_node(points[i], self.now);
+                stops.push((s[i], node));
+            }
+            stop_of[i] = stops.len() - 1;
+        }
+        for w in 0..stops.len().saturating_sub(1) {
+            let mid = (stops[w].0 + stops[w + 1].0) / 2.0;
+            let covering = |forward: bool| (0..m).find(|&i| {
+                let (a, b) = (s[i], s[(i + 1) % m]);
+                if forward { a < mid && mid < b } else { b < mid && mid < a }
+            });
+            if let (Some(f), Some(b)) = (covering(true), covering(false)) {
+                let edges = [self.vertices[cycle[f]].right, self.vertices[cycle[b]].right];
+                self.arcs.push((stops[w].1, stops[w + 1].1, edges));
+            }
+        }
+        for (i, &v) in cycle.iter().enumerate() {
+            self.finish(v, stops[stop_of[i]].1);
+        }
+    }
+
+    // t 时刻的波前，每个环是一个闭合回路
+    fn snapshot(&self, t: f64) -> Polygen {
+        let mut visited = vec![false; self.vertices.len()];
+        let mut result = Vec::new();
+        for v in 0..self.vertices.len() {
+            if !self.vertices[v].alive || visited[v] { continue; }
+            let cycle = self.cycle(v);
+            for &c in &cycle { visited[c] = true; }
+            if cycle.iter().any(|&c| self.lines[self.vertices[c].left].frame || self.lines[self.vertices[c].right].frame) { continue; }
+            let mut ring: Path = Vec::new();
+            for &c in &cycle {
+                let p = to_point(self.position(c, t));
+                if ring.last() != Some(&p) { ring.push(p); }
+            }
+            while ring.len() > 1 && ring[0] == ring[ring.len() - 1] { ring.pop(); }
+            if ring.len() < 3 { continue; }
+            let ring = close_ring(ring);
+            if path_signed_area(&ring) != 0.0 { result.push(ring); }
+        }
+        result
+    }
+
+    // 输入边 k 的面：从边的起点、终点出发，沿两侧之一是 k 的骨架边走回起点
+    fn face(&self, k: usize) -> SkeletonFace {
+        let line = &self.lines[k];
+        let arcs: Vec<(usize, usize)> = self.arcs.iter()
+            .filter(|(_, _, edges)| edges[0] != edges[1] && edges.contains(&k))
+            .map(|&(from, to, _)| (from, to))
+            .collect();
+        let mut used = vec![false; arcs.len()];
+        let mut nodes = vec![line.start_node, line.end_node];
+        let mut cur = line.end_node;
+        while cur != line.start_node {
+            let found = (0..arcs.len()).find(|&i| !used[i] && (arcs[i].0 == cur || arcs[i].1 == cur));
+            match found {
+                Some(i) => {
+                    used[i] = true;
+                    cur = if arcs[i].0 == cur { arcs[i].1 } else { arcs[i].0 };
+                    if cur != line.start_node { nodes.push(cur); }
+                }
+                None => break,
+            }
+        }
+        SkeletonFace { edge: line.id, path: close_ring(nodes.iter().map(|&n| to_point(self.nodes[n].0)).collect()) }
+    }
+}
@@ -0,0 +1,122 @@
+// 用直线、半平面或任意折线切分多边形。
+// 半平面用一个足够大的四边形表示（一条边在切割直线上，其余边远离多边形），
+// 再用布尔运算求交，因此切分结果与 boolean 一样是合法的带洞多边形。
+// 直线 a→b 的左侧是满足 orient(a, b, x) > 0 的一侧，即沿 a→b 走时逆时针回路内部所在的一侧。
+// 折线切分（刀具）把多边形边界和折线一起构造平面剖分，折线分隔开的每个区域成为一个多边形。
+
+use std::collections::HashMap;
+
+use crate::arrangement::{polygen_segments, Arrangement, Segment};
+use crate::boolean::{boolean, BooleanOp};
+use crate::geom::{to_f64, to_point};
+use crate::measure::polygen_bounding_box;
+use crate::multi::split_polygens;
+use crate::{Path, Point, Polygen};
+
+pub struct PolylineSplit {
+    // 切分得到的各块，外环逆时针、洞顺时针
+    pub pieces: Vec<Polygen>,
+    // 落在多边形内部、却没有把任何区域分开的切割线（例如只切进去一半），这些部分被忽略
+    pub ignored: Vec<Path>,
+}
+
+// 返回 (左侧的各块, 右侧的各块)；a、b 重合时无法确定直线，全部归入左侧
+pub fn split_by_line(polygen: &Polygen, a: Point, b: Point) -> (Vec<Polygen>, Vec<Polygen>) {
+    if a == b { return (split_polygens(polygen), Vec::new()); }
+    (clip_half_plane(polygen, a, b), clip_half_plane(polygen, b, a))
+}
+
+// 只保留直线 a→b 左侧的部分
+pub fn clip_half_plane(polygen: &Polygen, a: Point, b: Point) -> Vec<Polygen> {
+    let plane = match half_plane(polygen, a, b) {
+        Some(plane) => plane,
+        None => return Vec::new(),
+    };
+    split_polygens(&boolean(polygen, &vec![plane], BooleanOp::Intersection))
+}
+
+// 覆盖多边形在直线左侧部分的逆时针四边形，多边形为空或 a、b 重合时返回 None
+fn half_plane(polygen: &Polygen, a: Point, b: Point) -> Option<Path> {
+    let bbox = polygen_bounding_box(polygen)?;
+    let (a, b) = (to_f64(a), to_f64(b));
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let len = dx.hypot(dy);
+    if len == 0.0 { return None; }
+    let dir = [dx / len, dy / len];
+    // orient(a, b, a + normal) > 0
+    let normal = [dir[1], -dir[0]];
+    // 以多边形包围盒中心在直线上的投影为中点，半径取包围盒对角线的两倍加上中心到直线的距离
+    let center = to_f64(bbox.center());
+    let along = (center[0] - a[0]) * dir[0] + (center[1] - a[1]) * dir[1];
+    let foot = [a[0] + along * dir[0], a[1] + along * dir[1]];
+    let offset = ((center[0] - foot[0]) * normal[0] + (center[1] - foot[1]) * normal[1]).abs();
+    let radius = 2.0 * (bbox.width() as f64).hypot(bbox.height() as f64) + offset + 1.0;
+    let p1 = [foot[0] - radius * dir[0], foot[1] - radius * dir[1]];
+    let p2 = [foot[0] + radius * dir[0], foot[1] + radius * dir[1]];
+    let p3 = [p2[0] + radius * normal[0], p2[1] + radius * normal[1]];
+    let p4 = [p1[0] + radius * normal[0], p1[1] + radius * normal[1]];
+    Some(vec![to_point(p1), to_point(p2), to_point(p3), to_point(p4), to_point(p1)])
+}
+
+// 沿开放折线切分，折线可以多次穿过边界、自身相交；在多边形外部的部分没有影响
+pub fn split_by_polyline(polygen: &Polygen, line: &Path) -> PolylineSplit {
+    let mut segments = polygen_segments(polygen, 1);
+    for w in line.windows(2) {
+        segments.push(Segment { from: to_f64(w[0]), to: to_f64(w[1]), winding: 0 });
+    }
+    let arrangement = Arrangement::new(&segments);
+    let faces = arrangement.faces();
+    let inside = |f: Option<usize>| f.is_some_and(|f| faces.faces[f].winding != 0);
+
+    // 保留多边形的边界，以及两侧都在多边形内、分隔开两个不同区域的切割线；
+    // 两侧是同一个区域的切割线没有切开任何东西，去掉后重新构造
+    let mut kept = Vec::new();
+    let mut dangling = Vec::new();
+    for (edge, &[left, right]) in arrangement.edges.iter().zip(&faces.edge_faces) {
+        if inside(left) != inside(right) || (inside(left) && left != right) {
+            kept.push(*edge);
+        } else if inside(left) {
+            dangling.push((edge.from, edge.to));
+        }
+    }
+    let arrangement = arrangement.with_edges(kept);
+    let pieces = arrangement.faces().faces.iter()
+        .filter(|face| face.winding != 0)
+        .map(|face| arrangement.face_polygen(face))
+        .collect();
+    let ignored = chains(&dangling).into_iter()
+        .map(|chain| chain.iter().map(|&v| to_point(arrangement.vertices[v])).collect())
+        .collect();
+    PolylineSplit { pieces, ignored }
+}
+
+// 把边连成尽量长的折线（顶点下标），从度数不为2的顶点开始走，剩下的是闭合的环
+fn chains(edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut incident: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (k, &(u, v)) in edges.iter().enumerate() {
+        incident.entry(u).or_default().push(k);
+        incident.entry(v).or_default().push(k);
+    }
+    let mut starts: Vec<usize> = incident.keys().copied().collect();
+    starts.sort_by_key(|v| (incident[v].len() == 2, *v));
+    let mut used = vec![false; edges.len()];
+    let mut result = Vec::new();
+    for start in starts {
+        while let Some(&first) = incident[&start].iter().find(|&&k| !used[k]) {
+            let mut chain = vec![start];
+            let (mut cur, mut k) = (start, first);
+            loop {
+                used[k] = true;
+                cur = if edges[k].0 == cur { edges[k].1 } else { edges[k].0 };
+                chain.push(cur);
+                if incident[&cur].len() != 2 { break; }
+                match incident[&cur].iter().find(|&&k| !used[k]) {
+                    Some(&next) => k = next,
+                    None => break,
+                }
+            }
+            result.push(chain);
+        }
+    }
+    result
+}
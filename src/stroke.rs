@@ -0,0 +1,178 @@
+// 描边：把有宽度的折线（道路、笔画）转换为多边形轮廓，然后就可以参与裁剪。
+// 每一段生成一个矩形，转角处在外侧补上连接（尖角、圆角或斜角），两端加上线帽，
+// 这些凸多边形可能互相重叠（折线自交、急转弯），最后在平面剖分中按非零规则合并为合法的带洞多边形。
+
+use crate::arrangement::{Arrangement, Segment};
+use crate::geom::{orient_f64, to_f64};
+use crate::{Path, Polygen};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LineCap {
+    // 在端点处平齐截断
+    Butt,
+    // 以端点为圆心、半个线宽为半径的半圆
+    Round,
+    // 向外延伸半个线宽的方头
+    Square,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LineJoin {
+    // 尖角，尖角长度与线宽之比超过 limit 时改为斜角（与 SVG 的 stroke-miterlimit 相同）
+    Miter { limit: f32 },
+    Round,
+    Bevel,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StrokeOptions {
+    pub width: f32,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    // 圆帽、圆角展平为折线时的最大弦高误差
+    pub tolerance: f32,
+}
+
+impl Default for StrokeOptions {
+    fn default() -> StrokeOptions {
+        StrokeOptions { width: 1.0, cap: LineCap::Butt, join: LineJoin::Miter { limit: 4.0 }, tolerance: 0.1 }
+    }
+}
+
+// 首末点重合（至少3个不同的顶点）的折线视为闭合，首末点处也用连接而不加线帽。
+// 只有一个不同的点时，圆帽得到一个圆、方帽得到一个正方形、平齐截断得到空的结果
+pub fn stroke(line: &Path, options: &StrokeOptions) -> Polygen {
+    let h = options.width as f64 / 2.0;
+    let tolerance = (options.tolerance as f64).max(h * 1e-6);
+    let mut points: Vec<[f64; 2]> = Vec::new();
+    for &p in line {
+        let p = to_f64(p);
+        if points.last() != Some(&p) { points.push(p); }
+    }
+    if h.is_nan() || h <= 0.0 || points.is_empty() { return Vec::new(); }
+
+    let mut pieces: Vec<Vec<[f64; 2]>> = Vec::new();
+    if points.len() == 1 {
+        let c = points[0];
+        match options.cap {
+            LineCap::Butt => (),
+            LineCap::Round => pieces.push(arc(c, h, [1.0, 0.0], std::f64::consts::TAU, tolerance)),
+            LineCap::Square => pieces.push(vec![[c[0] - h, c[1] - h], [c[0] + h, c[1] - h], [c[0] + h, c[1] + h], [c[0] - h, c[1] + h]]),
+        }
+    } else {
+        let closed = points.len() > 3 && points[0] == points[points.len() - 1];
+        if closed { points.pop(); }
+        let m = points.len();
+        let count = if closed { m } else { m - 1 };
+        let directions: Vec<[f64; 2]> = (0..count).map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % m]);
+            let len = (b[0] - a[0]).hypot(b[1] - a[1]);
+            [(b[0] - a[0]) / len, (b[1] - a[1]) / len]
+        }).collect();
+        for (i, &d) in directions.iter().enumerate() {
+            let (a, b) = (points[i], points[(i + 1) % m]);
+            let n = normal(d);
+            pieces.push(vec![offset(a, n, h), offset(a, n, -h), offset(b, n, -h), offset(b, n, h)]);
+        }
+        let joints: Vec<usize> = if closed { (0..m).collect() } else { (1..m - 1).collect() };
+        for i in joints {
+            let d0 = directions[(i + count - 1) % count];
+            let d1 = directions[i % count];
+            if let Some(piece) = join(points[i], d0, d1, h, options.join, tolerance) { pieces.push(piece); }
+        }
+        if !closed {
+            let back = |d: [f64; 2]| [-d[0], -d[1]];
+            pieces.extend(cap(points[0], back(directions[0]), h, options.cap, tolerance));
+            pieces.extend(cap(points[m - 1], directions[count - 1], h, options.cap, tolerance));
+        }
+    }
+
+    // 每块都调整为逆时针，重叠的部分环绕数大于1
+    let mut segments = Vec::new();
+    for mut piece in pieces {
+        let area: f64 = (1..piece.len().saturating_sub(1)).map(|k| orient_f64(piece[0], piece[k], piece[k + 1])).sum();
+        if area == 0.0 { continue; }
+        if area < 0.0 { piece.reverse(); }
+        for k in 0..piece.len() {
+            segments.push(Segment { from: piece[k], to: piece[(k + 1) % piece.len()], winding: 1 });
+        }
+    }
+    Arrangement::new(&segments).dissolve(|winding| winding != 0).into_iter().flatten().collect()
+}
+
+// 方向 d 的左侧单位法向
+fn normal(d: [f64; 2]) -> [f64; 2] {
+    [d[1], -d[0]]
+}
+
+fn offset(p: [f64; 2], n: [f64; 2], distance: f64) -> [f64; 2] {
+    [p[0] + n[0] * distance, p[1] + n[1] * distance]
+}
+
+// 从 d0 方向的段转到 d1 方向的段时，在转角外侧补上的部分
+fn join(p: [f64; 2], d0: [f64; 2], d1: [f64; 2], h: f64, style: LineJoin, tolerance: f64) -> Option<Vec<[f64; 2]>> {
+    let turn = orient_f64([0.0, 0.0], d0, d1);
+    let straight = d0[0] * d1[0] + d0[1] * d1[1];
+    // 直行不需要连接；向左转时外侧在右边
+    if turn == 0.0 && straight > 0.0 { return None; }
+    let side = if turn > 0.0 { -1.0 } else { 1.0 };
+    let (n0, n1) = (normal(d0), normal(d1));
+    let (a, b) = (offset(p, n0, side * h), offset(p, n1, side * h));
+    match style {
+        LineJoin::Bevel => Some(vec![p, a, b]),
+        LineJoin::Miter { limit } => {
+            // 尖角顶点在两条偏移线的交点，到 p 的距离是 h / cos(φ/2)，φ 为两条法向的夹角
+            let cos = n0[0] * n1[0] + n0[1] * n1[1];
+            let ratio = (2.0 / (1.0 + cos)).sqrt();
+            if 1.0 + cos <= 1e-12 || ratio > limit as f64 { return Some(vec![p, a, b]); }
+            let k = side * h / (1.0 + cos);
+            Some(vec![p, a, [p[0] + (n0[0] + n1[0]) * k, p[1] + (n0[1] + n1[1]) * k], b])
+        }
+        LineJoin::Round => {
+            // 从 a 绕 p 转到 b，经过外侧；折返时两侧对称，取经过前进方向的一侧
+            let from = [n0[0] * side, n0[1] * side];
+            let mut sweep = orient_f64([0.0, 0.0], n1, n0).atan2(n0[0] * n1[0] + n0[1] * n1[1]);
+            if turn == 0.0 && arc_direction(from, sweep / 2.0, d0) < 0.0 { sweep = -sweep; }
+            let mut piece = arc(p, h, from, sweep, tolerance);
+            piece.push(p);
+            Some(piece)
+        }
+    }
+}
+
+// 在端点 p 处朝 d 方向（离开折线的方向）加的线帽
+fn cap(p: [f64; 2], d: [f64; 2], h: f64, style: LineCap, tolerance: f64) -> Option<Vec<[f64; 2]>> {
+    let n = normal(d);
+    match style {
+        LineCap::Butt => None,
+        LineCap::Square => {
+            let q = offset(p, d, h);
+            Some(vec![offset(p, n, h), offset(q, n, h), offset(q, n, -h), offset(p, n, -h)])
+        }
+        LineCap::Round => {
+            let mut sweep = std::f64::consts::PI;
+            if arc_direction(n, sweep / 2.0, d) < 0.0 { sweep = -sweep; }
+            Some(arc(p, h, n, sweep, tolerance))
+        }
+    }
+}
+
+// 单位向量 from 转过 angle 后与 d 的点积
+fn arc_direction(from: [f64; 2], angle: f64, d: [f64; 2]) -> f64 {
+    let (sin, cos) = angle.sin_cos();
+    (from[0] * cos - from[1] * sin) * d[0] + (from[0] * sin + from[1] * cos) * d[1]
+}
+
+// 圆心 c、半径 r，从单位方向 from 转过 sweep（弧度，正向为 atan2 的增大方向），包含两端点；整圆时不重复起点
+fn arc(c: [f64; 2], r: f64, from: [f64; 2], sweep: f64, tolerance: f64) -> Vec<[f64; 2]> {
+    // 弦高 r(1 - cos(θ/2)) 不超过 tolerance
+    let max_step = if tolerance >= r { std::f64::consts::FRAC_PI_2 } else { 2.0 * (1.0 - tolerance / r).acos() };
+    let count = (sweep.abs() / max_step).ceil().max(1.0) as usize;
+    let full = sweep.abs() >= std::f64::consts::TAU;
+    let start = from[1].atan2(from[0]);
+    let last = if full { count - 1 } else { count };
+    (0..=last).map(|i| {
+        let angle = start + sweep * i as f64 / count as f64;
+        [c[0] + r * angle.cos(), c[1] + r * angle.sin()]
+    }).collect()
+}
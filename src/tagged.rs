@@ -0,0 +1,121 @@
+// 回路上附带用户数据（地块编号、图层、材质等），布尔运算后每个结果多边形报告它来自哪些输入回路，
+// 便于在求交之后做属性连接。
+// 一个输入回路与结果多边形相关，当且仅当满足下面任一条：
+// 回路的边界（顶点或与另一输入的交点）出现在结果多边形上；
+// 结果多边形在这个输入内部，而该回路是包含它的最小外环；
+// 回路整个落在结果多边形内部（例如求并时被完全包住的回路）。
+
+use crate::boolean::{boolean_with_sources, BooleanOp};
+use crate::geom::{ring_vertices, ring_winding_number, to_f64, to_point};
+use crate::measure::{path_area, path_signed_area};
+use crate::multi::split_indices;
+use crate::query::{contains, Location};
+use crate::{Point, Polygen, VertexSource};
+
+// data[i] 是 rings[i] 的用户数据
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaggedPolygen<T> {
+    pub rings: Polygen,
+    pub data: Vec<T>,
+}
+
+impl<T> TaggedPolygen<T> {
+    pub fn new(rings: Polygen, data: Vec<T>) -> TaggedPolygen<T> {
+        assert_eq!(rings.len(), data.len(), "每个回路都要有对应的数据");
+        TaggedPolygen { rings, data }
+    }
+}
+
+impl<T: Clone> TaggedPolygen<T> {
+    // 所有回路使用同一个数据
+    pub fn uniform(rings: Polygen, value: T) -> TaggedPolygen<T> {
+        let data = vec![value; rings.len()];
+        TaggedPolygen { rings, data }
+    }
+}
+
+// 一个结果多边形（外环在前，之后是洞），以及与之相关的输入回路下标（升序）和对应的数据
+#[derive(Clone, Debug)]
+pub struct TaggedResult<'a, S, C> {
+    pub polygen: Polygen,
+    pub subject_rings: Vec<usize>,
+    pub clip_rings: Vec<usize>,
+    pub subject_data: Vec<&'a S>,
+    pub clip_data: Vec<&'a C>,
+}
+
+pub fn boolean_tagged<'a, S, C>(subject: &'a TaggedPolygen<S>, clip: &'a TaggedPolygen<C>, op: BooleanOp) -> Vec<TaggedResult<'a, S, C>> {
+    let (rings, sources) = boolean_with_sources(&subject.rings, &clip.rings, op);
+    split_indices(&rings).into_iter().map(|members| {
+        let polygen: Polygen = members.iter().map(|&i| rings[i].clone()).collect();
+        let mut subject_rings = Vec::new();
+        let mut clip_rings = Vec::new();
+        for &i in &members {
+            for source in &sources[i] {
+                match *source {
+                    VertexSource::Main { ring, .. } => subject_rings.push(ring),
+                    VertexSource::Clipper { ring, .. } => clip_rings.push(ring),
+                    VertexSource::Intersection { main_ring, clipper_ring, .. } => {
+                        subject_rings.push(main_ring);
+                        clip_rings.push(clipper_ring);
+                    }
+                }
+            }
+        }
+        if let Some(probe) = interior_point(&polygen) {
+            subject_rings.extend(enclosing_shell(&subject.rings, probe));
+            clip_rings.extend(enclosing_shell(&clip.rings, probe));
+        }
+        subject_rings.extend(enclosed_rings(&subject.rings, &polygen));
+        clip_rings.extend(enclosed_rings(&clip.rings, &polygen));
+        subject_rings.sort_unstable();
+        subject_rings.dedup();
+        clip_rings.sort_unstable();
+        clip_rings.dedup();
+        TaggedResult {
+            subject_data: subject_rings.iter().map(|&i| &subject.data[i]).collect(),
+            clip_data: clip_rings.iter().map(|&i| &clip.data[i]).collect(),
+            polygen,
+            subject_rings,
+            clip_rings,
+        }
+    }).collect()
+}
+
+// 多边形内部的一个点：在相邻两个顶点y坐标间隔最大处取一条水平扫描线，它不经过任何顶点；
+// 与各回路的边的交点排序后两两组成内部区间，取最长区间的中点。结果多边形没有重叠的回路，奇偶规则即可
+fn interior_point(polygen: &Polygen) -> Option<Point> {
+    let mut ys: Vec<f64> = polygen.iter().flat_map(|path| ring_vertices(path).iter().map(|&p| to_f64(p)[1])).collect();
+    ys.sort_by(|a, b| a.total_cmp(b));
+    ys.dedup();
+    let y = ys.windows(2).max_by(|a, b| (a[1] - a[0]).total_cmp(&(b[1] - b[0]))).map(|w| (w[0] + w[1]) / 2.0)?;
+    let mut xs = Vec::new();
+    for path in polygen {
+        let vertices = ring_vertices(path);
+        for i in 0..vertices.len() {
+            let (a, b) = (to_f64(vertices[i]), to_f64(vertices[(i + 1) % vertices.len()]));
+            if (a[1] < y) != (b[1] < y) {
+                xs.push(a[0] + (y - a[1]) / (b[1] - a[1]) * (b[0] - a[0]));
+            }
+        }
+    }
+    xs.sort_by(|a, b| a.total_cmp(b));
+    xs.chunks_exact(2)
+        .max_by(|a, b| (a[1] - a[0]).total_cmp(&(b[1] - b[0])))
+        .map(|w| to_point([(w[0] + w[1]) / 2.0, y]))
+}
+
+// 点在 rings 构成的多边形内部时，包含它的面积最小的外环
+fn enclosing_shell(rings: &Polygen, probe: Point) -> Option<usize> {
+    if contains(rings, probe) != Location::Inside { return None; }
+    (0..rings.len())
+        .filter(|&i| path_signed_area(&rings[i]) > 0.0 && ring_winding_number(&rings[i], probe) != 0)
+        .min_by(|&a, &b| path_area(&rings[a]).total_cmp(&path_area(&rings[b])))
+}
+
+// 所有顶点都严格在 polygen 内部的回路
+fn enclosed_rings(rings: &Polygen, polygen: &Polygen) -> Vec<usize> {
+    (0..rings.len())
+        .filter(|&i| rings[i].len() > 1 && rings[i].iter().all(|&p| contains(polygen, p) == Location::Inside))
+        .collect()
+}
@@ -0,0 +1,199 @@
+// 梯形分解与y单调分解，用于扫描线算法和快速点定位。
+// 过每个顶点作水平线，把多边形切成若干水平带，每条带内相邻的两条边之间（在多边形内部的部分）是一个梯形；
+// 上下相邻、左右两条边都相同的梯形合并为一个。梯形的上下底是水平的，左右腰是原多边形的边。
+// y单调块由上下唯一相邻的梯形串接而成，每条水平线与它的交集是一个区间。
+
+use crate::geom::{close_ring, ring_vertices, to_f64, to_point};
+use crate::measure::path_signed_area;
+use crate::{EdgeRef, Path, Point, Polygen};
+
+pub struct Trapezoid {
+    // 逆时针闭合回路，上底或下底退化为一点时是三角形
+    pub path: Path,
+    // 上下底的y坐标，y_min < y_max
+    pub y_min: f32,
+    pub y_max: f32,
+    // 左右两腰所在的原始边
+    pub left: EdgeRef,
+    pub right: EdgeRef,
+}
+
+pub struct MonotonePiece {
+    // 逆时针闭合回路
+    pub path: Path,
+    // 围成这一块的原始边（不含水平边），先左侧从上到下，再右侧从上到下
+    pub left: Vec<EdgeRef>,
+    pub right: Vec<EdgeRef>,
+}
+
+// 非水平的边，a 为y较小的端点
+#[derive(Copy, Clone)]
+struct SweepEdge {
+    a: [f64; 2],
+    b: [f64; 2],
+    id: EdgeRef,
+    // 沿回路方向向下（y增大）为 +1，向上为 -1
+    winding: i32,
+}
+
+impl SweepEdge {
+    fn x_at(&self, y: f64) -> f64 {
+        if y <= self.a[1] { return self.a[0]; }
+        if y >= self.b[1] { return self.b[0]; }
+        self.a[0] + (y - self.a[1]) * (self.b[0] - self.a[0]) / (self.b[1] - self.a[1])
+    }
+}
+
+// 按 y_min 从小到大输出，同一条带内从左到右
+pub fn trapezoidal_decomposition(polygen: &Polygen) -> Vec<Trapezoid> {
+    let (edges, cells) = sweep(polygen);
+    cells.iter().map(|cell| {
+        let (l, r) = (&edges[cell.left], &edges[cell.right]);
+        let corners = [[l.x_at(cell.y0), cell.y0], [l.x_at(cell.y1), cell.y1], [r.x_at(cell.y1), cell.y1], [r.x_at(cell.y0), cell.y0]];
+        Trapezoid {
+            path: to_ring(&corners),
+            y_min: cell.y0 as f32,
+            y_max: cell.y1 as f32,
+            left: l.id,
+            right: r.id,
+        }
+    }).collect()
+}
+
+pub fn monotone_decomposition(polygen: &Polygen) -> Vec<MonotonePiece> {
+    let (edges, cells) = sweep(polygen);
+    // 上下相邻：上面一块的下底与下面一块的上底在同一高度，且水平方向有重叠
+    let touches = |upper: &Cell, lower: &Cell| {
+        if upper.y1 != lower.y0 { return false; }
+        let y = upper.y1;
+        let lo = edges[upper.left].x_at(y).max(edges[lower.left].x_at(y));
+        let hi = edges[upper.right].x_at(y).min(edges[lower.right].x_at(y));
+        hi > lo
+    };
+    // cells 按 y0 排列，只需在 y0 等于上一块 y1 的范围内查找
+    let below: Vec<Vec<usize>> = cells.iter().map(|upper| {
+        let start = cells.partition_point(|c| c.y0 < upper.y1);
+        let end = cells.partition_point(|c| c.y0 <= upper.y1);
+        (start..end).filter(|&k| touches(upper, &cells[k])).collect()
+    }).collect();
+    let mut above: Vec<Vec<usize>> = vec![Vec::new(); cells.len()];
+    for (u, list) in below.iter().enumerate() {
+        for &k in list { above[k].push(u); }
+    }
+    // 只有一个上邻、且这个上邻只有它一个下邻时接在上邻后面，否则开始新的一块
+    let continues = |k: usize| above[k].len() == 1 && below[above[k][0]].len() == 1;
+    let mut pieces = Vec::new();
+    for start in (0..cells.len()).filter(|&k| !continues(k)) {
+        let mut chain = vec![start];
+        let mut cur = start;
+        while below[cur].len() == 1 && continues(below[cur][0]) {
+            cur = below[cur][0];
+            chain.push(cur);
+        }
+        pieces.push(monotone_piece(&edges, &cells, &chain));
+    }
+    pieces
+}
+
+// 合并后的梯形：左右两条边（下标）和y范围
+struct Cell {
+    left: usize,
+    right: usize,
+    y0: f64,
+    y1: f64,
+}
+
+fn sweep(polygen: &Polygen) -> (Vec<SweepEdge>, Vec<Cell>) {
+    let mut edges = Vec::new();
+    for (ring, path) in polygen.iter().enumerate() {
+        let vertices = ring_vertices(path);
+        if vertices.len() < 3 { continue; }
+        for i in 0..vertices.len() {
+            let (p, q) = (to_f64(vertices[i]), to_f64(vertices[(i + 1) % vertices.len()]));
+            if p[1] == q[1] { continue; }
+            let id = EdgeRef { ring, edge: i };
+            edges.push(if p[1] < q[1] { SweepEdge { a: p, b: q, id, winding: 1 } } else { SweepEdge { a: q, b: p, id, winding: -1 } });
+        }
+    }
+    let mut ys: Vec<f64> = edges.iter().flat_map(|e| [e.a[1], e.b[1]]).collect();
+    ys.sort_by(f64::total_cmp);
+    ys.dedup();
+
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|&i, &j| edges[i].a[1].total_cmp(&edges[j].a[1]));
+    let mut next = 0;
+    let mut active: Vec<usize> = Vec::new();
+    let mut cells: Vec<Cell> = Vec::new();
+    // 上一条带中的梯形在 cells 中的下标，用于与本条带中左右边相同的梯形合并
+    let mut open: Vec<usize> = Vec::new();
+    for w in ys.windows(2) {
+        let (y0, y1) = (w[0], w[1]);
+        active.retain(|&e| edges[e].b[1] > y0);
+        while next < order.len() && edges[order[next]].a[1] <= y0 {
+            active.push(order[next]);
+            next += 1;
+        }
+        let mid = (y0 + y1) / 2.0;
+        active.sort_by(|&i, &j| edges[i].x_at(mid).total_cmp(&edges[j].x_at(mid)));
+        let mut winding = 0;
+        let mut current = Vec::new();
+        for k in 0..active.len() {
+            winding += edges[active[k]].winding;
+            if winding == 0 || k + 1 == active.len() { continue; }
+            let (left, right) = (active[k], active[k + 1]);
+            match open.iter().find(|&&c| cells[c].left == left && cells[c].right == right && cells[c].y1 == y0) {
+                Some(&c) => {
+                    cells[c].y1 = y1;
+                    current.push(c);
+                }
+                None => {
+                    current.push(cells.len());
+                    cells.push(Cell { left, right, y0, y1 });
+                }
+            }
+        }
+        open = current;
+    }
+    (edges, cells)
+}
+
+fn monotone_piece(edges: &[SweepEdge], cells: &[Cell], chain: &[usize]) -> MonotonePiece {
+    // side 为 true 取左边，否则取右边；同一条边上的中间点是共线的，不输出
+    let side_chain = |left: bool| {
+        let mut points = Vec::new();
+        let mut ids = Vec::new();
+        let mut prev: Option<&Cell> = None;
+        for &c in chain {
+            let cell = &cells[c];
+            let edge = if left { cell.left } else { cell.right };
+            let prev_edge = prev.map(|p| if left { p.left } else { p.right });
+            if prev_edge != Some(edge) {
+                if let Some(p) = prev { points.push([edges[prev_edge.unwrap()].x_at(p.y1), p.y1]); }
+                points.push([edges[edge].x_at(cell.y0), cell.y0]);
+                ids.push(edges[edge].id);
+            }
+            prev = Some(cell);
+        }
+        let last = &cells[*chain.last().unwrap()];
+        let edge = if left { last.left } else { last.right };
+        points.push([edges[edge].x_at(last.y1), last.y1]);
+        (points, ids)
+    };
+    let (mut points, left) = side_chain(true);
+    let (right_points, right) = side_chain(false);
+    points.extend(right_points.into_iter().rev());
+    MonotonePiece { path: to_ring(&points), left, right }
+}
+
+// 去掉相邻的重复点，闭合并统一为逆时针
+fn to_ring(points: &[[f64; 2]]) -> Path {
+    let mut ring: Vec<Point> = Vec::new();
+    for &p in points {
+        let p = to_point(p);
+        if ring.last() != Some(&p) { ring.push(p); }
+    }
+    while ring.len() > 1 && ring[0] == ring[ring.len() - 1] { ring.pop(); }
+    let mut ring = close_ring(ring);
+    if path_signed_area(&ring) < 0.0 { ring.reverse(); }
+    ring
+}
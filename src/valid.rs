@@ -0,0 +1,108 @@
+// 多边形合法性检查与修复。
+// 合法的多边形：每个回路至少3个不同的顶点，任意两条边除相邻边共用端点外没有公共点，
+// 被偶数个其它回路包含的回路（外环）逆时针、被奇数个包含的（洞）顺时针。
+// 修复（make_valid）：在所有交点处打断边，按填充规则判断每个区域是否在内部，
+// 只保留内外交界处的边，再重新组织成带洞的多边形。
+
+use crate::arrangement::{polygen_segments, Arrangement};
+use crate::geom::{crossing_point, orient, ring_vertices, ring_winding_number, segments_intersect, to_f64};
+use crate::measure::path_signed_area;
+use crate::{Line, Point, Polygen};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    // 环绕数为奇数的区域在内部
+    EvenOdd,
+    // 环绕数不为0的区域在内部
+    NonZero,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidityIssue {
+    // 不足3个不同的顶点
+    TooFewVertices { ring: usize },
+    // 两条边相交、相接或重叠（同一回路中相邻的边共用端点不算，但折返重叠算），point 是一个公共点
+    Intersection { ring1: usize, edge1: usize, ring2: usize, edge2: usize, point: Point },
+    // 方向与嵌套层数不符（包括不在任何外环内的顺时针回路）
+    WrongOrientation { ring: usize },
+}
+
+pub fn is_valid(polygen: &Polygen) -> bool {
+    validate(polygen).is_empty()
+}
+
+// 列出所有问题，边 k 是从顶点 k 到顶点 k+1 的边（不计闭合的末点）
+pub fn validate(polygen: &Polygen) -> Vec<ValidityIssue> {
+    let mut issues = Vec::new();
+    let mut rings = Vec::new();
+    for (ring, path) in polygen.iter().enumerate() {
+        let vertices = ring_vertices(path);
+        let mut distinct = vertices.to_vec();
+        distinct.sort_by(|a, b| a.position[0].total_cmp(&b.position[0]).then(a.position[1].total_cmp(&b.position[1])));
+        distinct.dedup();
+        if distinct.len() < 3 {
+            issues.push(ValidityIssue::TooFewVertices { ring });
+        } else {
+            rings.push(ring);
+        }
+    }
+
+    // (回路, 边, 线段)，按x坐标扫描
+    let mut edges: Vec<(usize, usize, Line)> = Vec::new();
+    for &ring in &rings {
+        let vertices = ring_vertices(&polygen[ring]);
+        for i in 0..vertices.len() {
+            edges.push((ring, i, [vertices[i], vertices[(i + 1) % vertices.len()]]));
+        }
+    }
+    let min_x = |l: &Line| l[0].position[0].min(l[1].position[0]);
+    let max_x = |l: &Line| l[0].position[0].max(l[1].position[0]);
+    edges.sort_by(|a, b| min_x(&a.2).total_cmp(&min_x(&b.2)));
+    let mut intersections = Vec::new();
+    for i in 0..edges.len() {
+        let (ring1, edge1, l1) = edges[i];
+        for &(ring2, edge2, l2) in &edges[i + 1..] {
+            if min_x(&l2) > max_x(&l1) { break; }
+            if !segments_intersect(&l1, &l2) { continue; }
+            let n = ring_vertices(&polygen[ring1]).len();
+            let adjacent = ring1 == ring2 && ((edge1 + 1) % n == edge2 || (edge2 + 1) % n == edge1);
+            if adjacent && !folds_back(&l1, &l2) { continue; }
+            let (first, second) = if (ring1, edge1) < (ring2, edge2) { ((ring1, edge1), (ring2, edge2)) } else { ((ring2, edge2), (ring1, edge1)) };
+            intersections.push(ValidityIssue::Intersection {
+                ring1: first.0, edge1: first.1, ring2: second.0, edge2: second.1,
+                point: crossing_point(&l1, &l2),
+            });
+        }
+    }
+    intersections.sort_by_key(|issue| match *issue {
+        ValidityIssue::Intersection { ring1, edge1, ring2, edge2, .. } => (ring1, edge1, ring2, edge2),
+        _ => (0, 0, 0, 0),
+    });
+    issues.extend(intersections);
+
+    for &ring in &rings {
+        let probe = ring_vertices(&polygen[ring])[0];
+        let depth = rings.iter().filter(|&&other| other != ring && ring_winding_number(&polygen[other], probe) != 0).count();
+        if (path_signed_area(&polygen[ring]) > 0.0) != (depth % 2 == 0) {
+            issues.push(ValidityIssue::WrongOrientation { ring });
+        }
+    }
+    issues
+}
+
+// 相邻的两条边（共用一个端点）是否共线且方向相反，即形成零宽度的尖刺
+fn folds_back(l1: &Line, l2: &Line) -> bool {
+    let (shared, p, q) = if l1[1] == l2[0] { (l1[1], l1[0], l2[1]) } else { (l1[0], l1[1], l2[0]) };
+    if orient(p, shared, q) != 0.0 { return false; }
+    let (s, p, q) = (to_f64(shared), to_f64(p), to_f64(q));
+    (p[0] - s[0]) * (q[0] - s[0]) + (p[1] - s[1]) * (q[1] - s[1]) > 0.0
+}
+
+// 修复为若干个合法的多边形（外环在前，之后是它的洞）。
+// 环绕数按回路的方向计算，逆时针回路内部 +1、顺时针回路内部 -1，与 query::winding_number 相同
+pub fn make_valid(polygen: &Polygen, rule: FillRule) -> Vec<Polygen> {
+    Arrangement::new(&polygen_segments(polygen, 1)).dissolve(|winding| match rule {
+        FillRule::EvenOdd => winding % 2 != 0,
+        FillRule::NonZero => winding != 0,
+    })
+}
@@ -0,0 +1,92 @@
+use cg::attribute::{boolean_with_attributes, clipping_with_attributes, AttributePriority};
+use cg::boolean::BooleanOp;
+use cg::{Point, Polygen};
+
+mod common;
+use common::{assert_close, rect, ring};
+
+// 两个输入的属性分别是坐标的不同线性函数，沿边插值的结果仍在各自的平面上
+fn height_a(p: Point) -> f32 {
+    p.position[0] + 2.0 * p.position[1]
+}
+
+fn height_b(p: Point) -> f32 {
+    100.0 + 3.0 * p.position[0] - p.position[1]
+}
+
+fn attributes(polygen: &Polygen, height: fn(Point) -> f32) -> Vec<Vec<f32>> {
+    polygen.iter().map(|path| path[..path.len() - 1].iter().map(|&p| height(p)).collect()).collect()
+}
+
+fn inputs() -> (Polygen, Polygen) {
+    let a = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    let b = vec![ring(&[[5.0, -3.0], [-2.0, 4.0], [5.0, 13.0], [14.0, 6.0]], true)];
+    (a, b)
+}
+
+// 每个结果顶点要么是某个输入的顶点，要么是交点；交点的属性取 priority 指定的那个输入的平面
+fn assert_heights(a: &Polygen, b: &Polygen, result: &Polygen, heights: &[Vec<f32>], priority: AttributePriority) {
+    assert_eq!(result.len(), heights.len());
+    let is_vertex = |polygen: &Polygen, p: &Point| polygen.iter().flatten().any(|q| q == p);
+    let mut intersections = 0;
+    for (path, path_heights) in result.iter().zip(heights) {
+        assert_eq!(path.len(), path_heights.len());
+        for (&p, &h) in path.iter().zip(path_heights) {
+            let expected = if is_vertex(a, &p) {
+                height_a(p)
+            } else if is_vertex(b, &p) {
+                height_b(p)
+            } else {
+                intersections += 1;
+                match priority {
+                    AttributePriority::Main => height_a(p),
+                    AttributePriority::Clipper => height_b(p),
+                }
+            };
+            assert_close(h, expected);
+        }
+    }
+    assert!(intersections > 0);
+}
+
+#[test]
+fn boolean_interpolates_at_intersections_under_both_priorities() {
+    let (a, b) = inputs();
+    let (a_heights, b_heights) = (attributes(&a, height_a), attributes(&b, height_b));
+    for &op in &[BooleanOp::Intersection, BooleanOp::Union, BooleanOp::Difference] {
+        for &priority in &[AttributePriority::Main, AttributePriority::Clipper] {
+            let (result, heights) = boolean_with_attributes(&a, &a_heights, &b, &b_heights, op, priority);
+            assert!(!result.is_empty());
+            assert_heights(&a, &b, &result, &heights, priority);
+        }
+    }
+}
+
+#[test]
+fn clipping_interpolates_at_intersections_under_both_priorities() {
+    let (a, b) = inputs();
+    let (a_heights, b_heights) = (attributes(&a, height_a), attributes(&b, height_b));
+    for &priority in &[AttributePriority::Main, AttributePriority::Clipper] {
+        let (result, heights) = clipping_with_attributes(&a, &a_heights, &b, &b_heights, priority);
+        assert!(!result.is_empty());
+        assert_heights(&a, &b, &result, &heights, priority);
+    }
+}
+
+#[test]
+fn array_attributes_interpolate_per_component() {
+    // 纹理坐标取顶点坐标本身，插值后应与结果顶点重合
+    let (a, b) = inputs();
+    let position = |polygen: &Polygen| -> Vec<Vec<[f32; 2]>> {
+        polygen.iter().map(|path| path[..path.len() - 1].iter().map(|p| p.position).collect()).collect()
+    };
+    for &priority in &[AttributePriority::Main, AttributePriority::Clipper] {
+        let (result, uvs) = boolean_with_attributes(&a, &position(&a), &b, &position(&b), BooleanOp::Intersection, priority);
+        for (path, path_uvs) in result.iter().zip(&uvs) {
+            for (p, uv) in path.iter().zip(path_uvs) {
+                assert_close(uv[0], p.position[0]);
+                assert_close(uv[1], p.position[1]);
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use cg::boolean::{boolean, boolean_with_sources, cascaded_union, BooleanOp};
+use cg::measure::{polygen_area, polygen_signed_area};
+use cg::valid::validate;
+use cg::{is_path_anti_clockwise, Path, Point, Polygen, VertexSource};
+
+mod common;
+use common::{assert_close, hole, rect, ring};
+
+#[test]
+fn union_intersection_and_difference_of_two_rectangles() {
+    let a = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    let b = vec![rect(6.0, 2.0, 16.0, 12.0)];
+    assert_close(polygen_area(&boolean(&a, &b, BooleanOp::Intersection)), 32.0);
+    assert_close(polygen_area(&boolean(&a, &b, BooleanOp::Union)), 168.0);
+    assert_close(polygen_area(&boolean(&a, &b, BooleanOp::Difference)), 68.0);
+    assert_close(polygen_area(&boolean(&b, &a, BooleanOp::Difference)), 68.0);
+}
+
+#[test]
+fn rings_without_crossings_are_kept_or_dropped() {
+    let outer = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    let inner = vec![rect(2.0, 2.5, 5.0, 5.5)];
+    assert_close(polygen_area(&boolean(&outer, &inner, BooleanOp::Intersection)), 9.0);
+    assert_close(polygen_area(&boolean(&outer, &inner, BooleanOp::Union)), 100.0);
+    let ring_with_hole = boolean(&outer, &inner, BooleanOp::Difference);
+    assert_eq!(ring_with_hole.len(), 2);
+    assert_close(polygen_signed_area(&ring_with_hole), 91.0);
+    assert!(boolean(&inner, &outer, BooleanOp::Difference).is_empty());
+}
+
+#[test]
+fn rectangles_sharing_edges() {
+    let a = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    // a 的下半部分，三条边落在 a 的边上
+    let half = vec![rect(0.0, 5.0, 10.0, 10.0)];
+    let union = boolean(&a, &half, BooleanOp::Union);
+    assert_eq!(union.len(), 1);
+    assert_close(polygen_signed_area(&union), 100.0);
+    let difference = boolean(&a, &half, BooleanOp::Difference);
+    assert_eq!(difference.len(), 1);
+    assert_close(polygen_signed_area(&difference), 50.0);
+    assert_close(polygen_signed_area(&boolean(&a, &half, BooleanOp::Intersection)), 50.0);
+    assert!(boolean(&half, &a, BooleanOp::Difference).is_empty());
+
+    // 与 a 共三条边、向下伸出的长矩形
+    let tall = vec![rect(0.0, 0.0, 10.0, 20.0)];
+    assert_close(polygen_signed_area(&boolean(&a, &tall, BooleanOp::Intersection)), 100.0);
+    assert_close(polygen_signed_area(&boolean(&tall, &a, BooleanOp::Difference)), 100.0);
+
+    // 只共一条边的两个正方形，并集是一个回路
+    let right = vec![rect(10.0, 0.0, 20.0, 10.0)];
+    let union = boolean(&a, &right, BooleanOp::Union);
+    assert_eq!(validate(&union), Vec::new());
+    assert_eq!(union.len(), 1);
+    assert_close(polygen_signed_area(&union), 200.0);
+    assert!(boolean(&a, &right, BooleanOp::Intersection).is_empty());
+    assert_close(polygen_signed_area(&boolean(&a, &right, BooleanOp::Difference)), 100.0);
+}
+
+#[test]
+fn polygen_with_itself() {
+    let a = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    for &op in &[BooleanOp::Union, BooleanOp::Intersection] {
+        let result = boolean(&a, &a, op);
+        assert_eq!(result.len(), 1, "{:?}", op);
+        assert!(is_path_anti_clockwise(&result[0]), "{:?}", op);
+        assert_close(polygen_signed_area(&result), 100.0);
+    }
+    assert!(boolean(&a, &a, BooleanOp::Difference).is_empty());
+
+    let with_hole = vec![rect(0.0, 0.0, 10.0, 10.0), hole(3.0, 3.0, 6.0, 6.0)];
+    let union = boolean(&with_hole, &with_hole, BooleanOp::Union);
+    assert_eq!(validate(&union), Vec::new());
+    assert_eq!(union.len(), 2);
+    assert_close(polygen_signed_area(&union), 91.0);
+}
+
+// 边 edge 上参数 param 处的点
+fn point_on_edge(polygen: &Polygen, ring: usize, edge: usize, param: f32) -> Point {
+    let (s, e) = (polygen[ring][edge].position, polygen[ring][edge + 1].position);
+    Point::new([s[0] + (e[0] - s[0]) * param, s[1] + (e[1] - s[1]) * param])
+}
+
+// 每个结果顶点都能由它记录的来源还原出来，交点在两条边上的参数都要对
+fn assert_sources_match(a: &Polygen, b: &Polygen, result: &Polygen, sources: &[Vec<VertexSource>]) {
+    assert_eq!(result.len(), sources.len());
+    for (path, path_sources) in result.iter().zip(sources) {
+        assert_eq!(path.len(), path_sources.len());
+        for (p, source) in path.iter().zip(path_sources) {
+            let expected = match *source {
+                VertexSource::Main { ring, index } => vec![a[ring][index]],
+                VertexSource::Clipper { ring, index } => vec![b[ring][index]],
+                VertexSource::Intersection { main_ring, main_edge, main_param, clipper_ring, clipper_edge, clipper_param } => vec![
+                    point_on_edge(a, main_ring, main_edge, main_param),
+                    point_on_edge(b, clipper_ring, clipper_edge, clipper_param),
+                ],
+            };
+            for q in expected {
+                assert_close(p.position[0], q.position[0]);
+                assert_close(p.position[1], q.position[1]);
+            }
+        }
+    }
+}
+
+#[test]
+fn sources_of_result_vertices() {
+    let a = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    let b = vec![rect(6.0, 2.0, 16.0, 12.0)];
+    let (result, sources) = boolean_with_sources(&a, &b, BooleanOp::Intersection);
+    assert_sources_match(&a, &b, &result, &sources);
+    let intersections = sources.iter().flatten().filter(|s| matches!(s, VertexSource::Intersection { .. })).count();
+    // (10, 2) 和 (6, 10) 两个交点，闭合的末点可能重复一个
+    assert!(intersections >= 2);
+}
+
+#[test]
+fn sources_at_a_t_junction() {
+    // b 的顶点 (10, 5) 落在 a 的右边上，b 的另外两条边穿过 a 的上下两边
+    let a = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    let b = vec![ring(&[[10.0, 5.0], [4.0, 12.0], [4.0, -2.0]], true)];
+    let t_vertex = Point::new([10.0, 5.0]);
+    for &op in &[BooleanOp::Intersection, BooleanOp::Union, BooleanOp::Difference] {
+        let (result, sources) = boolean_with_sources(&a, &b, op);
+        assert_sources_match(&a, &b, &result, &sources);
+        let index = b[0].iter().position(|&p| p == t_vertex).unwrap();
+        let found = result.iter().flatten().zip(sources.iter().flatten()).find(|(&p, _)| p == t_vertex);
+        assert_eq!(found.map(|(_, &s)| s), Some(VertexSource::Clipper { ring: 0, index }), "{:?}", op);
+
+        // 交换两者后 T 形顶点来自主多边形；b - a 的结果不经过它
+        let (result, sources) = boolean_with_sources(&b, &a, op);
+        assert_sources_match(&b, &a, &result, &sources);
+        let found = result.iter().flatten().zip(sources.iter().flatten()).find(|(&p, _)| p == t_vertex);
+        let expected = if op == BooleanOp::Difference { None } else { Some(VertexSource::Main { ring: 0, index }) };
+        assert_eq!(found.map(|(_, &s)| s), expected, "{:?}", op);
+    }
+}
+
+#[test]
+fn cascaded_union_with_duplicates_and_neighbours() {
+    let a = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    let union = cascaded_union(&[a.clone(), a, vec![rect(10.0, 0.0, 20.0, 10.0)]]);
+    assert_eq!(validate(&union), Vec::new());
+    assert_eq!(union.len(), 1);
+    assert_close(polygen_signed_area(&union), 200.0);
+}
+
+#[test]
+fn cascaded_union_of_a_chain() {
+    // 每个正方形与下一个重叠 2 x 9.5，与更远的不重叠
+    let squares: Vec<Polygen> = (0..40).map(|i| {
+        let (x, y) = (8.0 * i as f32, 0.5 * i as f32);
+        vec![rect(x, y, x + 10.0, y + 10.0)]
+    }).collect();
+    let union = cascaded_union(&squares);
+    assert_eq!(union.len(), 1);
+    assert!(is_path_anti_clockwise(&union[0]));
+    assert_close(polygen_area(&union), 40.0 * 100.0 - 39.0 * 19.0);
+}
+
+#[test]
+fn cascaded_union_of_a_frame_has_a_hole() {
+    let pieces = vec![
+        vec![rect(0.0, 0.0, 30.0, 6.0)],
+        vec![rect(1.0, -1.0, 7.0, 29.0)],
+        vec![rect(0.5, 24.0, 30.5, 30.0)],
+        vec![rect(23.0, 2.0, 29.0, 32.0)],
+    ];
+    let union = cascaded_union(&pieces);
+    assert_eq!(union.len(), 2);
+    let holes: Vec<&Path> = union.iter().filter(|path| !is_path_anti_clockwise(path)).collect();
+    assert_eq!(holes.len(), 1);
+    assert_close(polygen_area(&union), 4.0 * 180.0 - (36.0 + 30.0 + 24.0 + 36.0));
+}
+
+#[test]
+fn cascaded_union_of_scattered_pairs() {
+    // 10 x 10 个互不相干的组，每组两个矩形重叠 3 x 4
+    let mut pieces = Vec::new();
+    for i in 0..10 {
+        for j in 0..10 {
+            let (x, y) = (50.0 * i as f32, 50.0 * j as f32);
+            pieces.push(vec![rect(x, y, x + 10.0, y + 10.0)]);
+            pieces.push(vec![rect(x + 7.0, y + 6.0, x + 17.0, y + 16.0)]);
+        }
+    }
+    let union = cascaded_union(&pieces);
+    assert_eq!(union.len(), 100);
+    assert_close(polygen_area(&union), 100.0 * (200.0 - 12.0));
+}
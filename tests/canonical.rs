@@ -0,0 +1,77 @@
+use cg::canonical::{canonicalize, canonicalize_path};
+use cg::{is_path_anti_clockwise, Path, Point, Polygen};
+
+mod common;
+use common::{closed, hole, rect};
+
+// 从第 k 个顶点开始重新排列闭合回路
+fn rotate(path: &Path, k: usize) -> Path {
+    let mut ring = path[..path.len() - 1].to_vec();
+    ring.rotate_left(k);
+    ring.push(ring[0]);
+    ring
+}
+
+fn reversed(path: &Path) -> Path {
+    path.iter().rev().copied().collect()
+}
+
+fn shape() -> Polygen {
+    vec![
+        closed(&[[0.0, 0.0], [1.0, 9.0], [10.0, 10.0], [12.0, 4.0], [6.0, -1.0]]),
+        hole(2.0, 2.0, 5.0, 5.0),
+        rect(20.0, 0.0, 24.0, 3.0),
+    ]
+}
+
+#[test]
+fn invariant_under_start_vertex_rotation() {
+    let expected = canonicalize(&shape());
+    for k in 0..4 {
+        let rotated: Polygen = shape().iter().map(|path| rotate(path, k % (path.len() - 1))).collect();
+        assert_eq!(canonicalize(&rotated), expected);
+    }
+}
+
+#[test]
+fn invariant_under_ring_reversal() {
+    let expected = canonicalize(&shape());
+    // 方向按嵌套层数决定：外环逆时针、洞顺时针，与输入的方向无关
+    let reversed_all: Polygen = shape().iter().map(reversed).collect();
+    assert_eq!(canonicalize(&reversed_all), expected);
+    assert!(is_path_anti_clockwise(&expected[0]));
+    let hole_ring = expected.iter().find(|path| path[0].position == [2.0, 2.0]).unwrap();
+    assert!(!is_path_anti_clockwise(hole_ring));
+}
+
+#[test]
+fn invariant_under_ring_order() {
+    let expected = canonicalize(&shape());
+    let mut shuffled = shape();
+    shuffled.rotate_left(1);
+    assert_eq!(canonicalize(&shuffled), expected);
+    shuffled.reverse();
+    assert_eq!(canonicalize(&shuffled), expected);
+}
+
+#[test]
+fn path_starts_at_smallest_vertex_and_normalizes_negative_zero() {
+    let path = closed(&[[3.0, 1.0], [-0.0, 2.0], [1.0, -0.0], [0.0, 0.5]]);
+    let canonical = canonicalize_path(&path, true);
+    assert_eq!(canonical[0].position, [0.0, 0.5]);
+    assert_eq!(canonical[0], canonical[canonical.len() - 1]);
+    assert!(canonical.iter().all(|p| p.position.iter().all(|c| c.is_sign_positive())));
+    assert_eq!(canonicalize_path(&reversed(&path), true), canonical);
+    assert_eq!(canonicalize_path(&path, false), reversed(&canonical));
+}
+
+#[test]
+fn self_touching_ring_picks_smallest_rotation() {
+    // 顶点 (0, 0) 出现两次
+    let path: Path = closed(&[[0.0, 0.0], [0.0, 4.0], [4.0, 4.0], [0.0, 0.0], [4.0, 0.0], [4.0, -4.0]]);
+    let canonical = canonicalize_path(&path, true);
+    for k in 0..6 {
+        assert_eq!(canonicalize_path(&rotate(&path, k), true), canonical);
+    }
+    assert_eq!(canonical[0], Point::new([0.0, 0.0]));
+}
@@ -0,0 +1,60 @@
+use cg::cleanup::{cleanup, cleanup_path, CleanupOptions};
+use cg::measure::{path_signed_area, polygen_signed_area};
+use cg::valid::is_valid;
+use cg::{Path, Point};
+
+mod common;
+use common::{assert_close, closed, hole, rect};
+
+fn vertices(path: &Path) -> Vec<[f32; 2]> {
+    path.iter().map(|p| p.position).collect()
+}
+
+#[test]
+fn duplicate_and_near_duplicate_vertices_collapse() {
+    let path = closed(&[[0.0, 0.0], [0.0, 0.0], [0.0, 4.0], [0.00001, 4.0], [4.0, 4.0], [4.0, 0.0], [4.0, 0.0], [0.0, 0.00001]]);
+    let cleaned = cleanup_path(&path, &CleanupOptions::default()).unwrap();
+    assert_eq!(vertices(&cleaned), vec![[0.0, 0.0], [0.0, 4.0], [4.0, 4.0], [4.0, 0.0], [0.0, 0.0]]);
+    assert_close(path_signed_area(&cleaned), 16.0);
+}
+
+#[test]
+fn spikes_are_removed_and_collinear_vertices_follow_the_option() {
+    // (0, 2) 是边上的共线点，(4, 2) 到 (7, 2) 再折回是尖刺
+    let path = closed(&[[0.0, 0.0], [0.0, 2.0], [0.0, 4.0], [4.0, 4.0], [4.0, 2.0], [7.0, 2.0], [4.0, 2.0], [4.0, 0.0]]);
+    let cleaned = cleanup_path(&path, &CleanupOptions::default()).unwrap();
+    assert_eq!(vertices(&cleaned), vec![[0.0, 0.0], [0.0, 4.0], [4.0, 4.0], [4.0, 0.0], [0.0, 0.0]]);
+
+    let options = CleanupOptions { remove_collinear: false, ..CleanupOptions::default() };
+    let kept = cleanup_path(&path, &options).unwrap();
+    assert_eq!(vertices(&kept), vec![[0.0, 0.0], [0.0, 2.0], [0.0, 4.0], [4.0, 4.0], [4.0, 2.0], [4.0, 0.0], [0.0, 0.0]]);
+    assert_close(path_signed_area(&kept), 16.0);
+}
+
+#[test]
+fn slivers_and_degenerate_rings_are_dropped() {
+    let sliver = closed(&[[0.0, 0.0], [0.0, 10.0], [0.05, 10.0], [0.05, 0.0]]);
+    assert!(cleanup_path(&sliver, &CleanupOptions::default()).is_some());
+    let options = CleanupOptions { min_area: 1.0, ..CleanupOptions::default() };
+    assert!(cleanup_path(&sliver, &options).is_none());
+    assert!(cleanup_path(&rect(0.0, 0.0, 2.0, 2.0), &options).is_some());
+    // 宽度在 epsilon 以内时两侧顶点合并，回路退化
+    assert!(cleanup_path(&sliver, &CleanupOptions { epsilon: 0.1, ..CleanupOptions::default() }).is_none());
+    // 所有顶点共线，删除后不足3个顶点
+    assert!(cleanup_path(&closed(&[[0.0, 0.0], [1.0, 1.0], [2.0, 2.0], [3.0, 3.0]]), &CleanupOptions::default()).is_none());
+}
+
+#[test]
+fn cleaned_polygen_with_hole_is_valid() {
+    let mut outer = rect(0.0, 0.0, 10.0, 10.0);
+    outer.insert(1, outer[0]);
+    outer.insert(3, Point::new([0.0, 10.0 + 1e-5]));
+    let mut inner = hole(3.0, 3.0, 6.0, 6.0);
+    inner.insert(1, Point::new([4.5, 3.0]));
+    let polygen = vec![outer, inner, closed(&[[20.0, 20.0], [20.0, 20.0001], [20.0001, 20.0]])];
+    let cleaned = cleanup(&polygen, &CleanupOptions::default());
+    assert_eq!(cleaned.len(), 2);
+    assert!(cleaned.iter().all(|path| path.len() == 5 && path[0] == path[4]));
+    assert!(is_valid(&cleaned));
+    assert_close(polygen_signed_area(&cleaned), 91.0);
+}
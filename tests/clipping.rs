@@ -0,0 +1,25 @@
+use cg::boolean::{boolean, BooleanOp};
+use cg::clipping;
+use cg::measure::polygen_area;
+
+mod common;
+use common::{assert_close, closed};
+
+// 两个多边形共用顶点 (6, 3)，顶点表在这里成环而回不到起点；只有保留走出的环才能得到交集
+#[test]
+fn loop_that_misses_the_start_is_kept() {
+    let a = vec![closed(&[[8.0, 5.0], [6.0, 3.0], [3.0, 4.0], [3.0, 7.0], [6.0, 8.0]])];
+    let b = vec![closed(&[[7.0, 5.0], [6.0, 3.0], [3.0, 3.0], [2.0, 7.0], [7.0, 10.0]])];
+    let result = clipping(&a, &b).result;
+    assert_close(polygen_area(&result), 15.25);
+    assert_close(polygen_area(&result), polygen_area(&boolean(&a, &b, BooleanOp::Intersection)));
+}
+
+// 从多个交点出发的尾巴汇入同一个环时，这个环只输出一次
+#[test]
+fn tails_into_an_earlier_loop_are_dropped() {
+    let a = vec![closed(&[[8.0, 5.0], [6.0, 2.0], [3.0, 3.0], [1.0, 8.0], [6.0, 7.0]])];
+    let b = vec![closed(&[[7.0, 5.0], [8.0, 1.0], [3.0, 2.0], [0.0, 5.0], [2.0, 9.0], [6.0, 7.0]])];
+    let result = clipping(&a, &b).result;
+    assert_close(polygen_area(&result), polygen_area(&boolean(&a, &b, BooleanOp::Intersection)));
+}
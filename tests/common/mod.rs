@@ -0,0 +1,39 @@
+// 各个测试共用的回路构造和断言，每个测试只用到其中一部分
+#![allow(dead_code)]
+
+use cg::measure::path_signed_area;
+use cg::{Path, Point};
+
+// 按给出的顺序闭合，不调整方向（用于自交、方向不对等需要原样保留的输入）
+pub fn closed(points: &[[f32; 2]]) -> Path {
+    let mut path = polyline(points);
+    path.push(path[0]);
+    path
+}
+
+// 闭合回路，anticlockwise 为 true 时统一为逆时针（外环），否则为顺时针（洞）
+pub fn ring(points: &[[f32; 2]], anticlockwise: bool) -> Path {
+    let mut path = closed(points);
+    if (path_signed_area(&path) > 0.0) != anticlockwise { path.reverse(); }
+    path
+}
+
+// 屏幕坐标系下逆时针的矩形
+pub fn rect(x0: f32, y0: f32, x1: f32, y1: f32) -> Path {
+    closed(&[[x0, y0], [x0, y1], [x1, y1], [x1, y0]])
+}
+
+// 顺时针的矩形（洞）
+pub fn hole(x0: f32, y0: f32, x1: f32, y1: f32) -> Path {
+    let mut path = rect(x0, y0, x1, y1);
+    path.reverse();
+    path
+}
+
+pub fn polyline(points: &[[f32; 2]]) -> Path {
+    points.iter().map(|&p| Point::new(p)).collect()
+}
+
+pub fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-2, "{} != {}", actual, expected);
+}
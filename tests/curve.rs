@@ -0,0 +1,116 @@
+use cg::curve::{flatten, flatten_path, CurvePath, Segment};
+use cg::Point;
+
+mod common;
+use common::assert_close;
+
+fn point(p: [f32; 2]) -> Point {
+    Point::new(p)
+}
+
+fn distance_to_chord(p: [f64; 2], a: [f64; 2], b: [f64; 2]) -> f64 {
+    let d = [b[0] - a[0], b[1] - a[1]];
+    let len2 = d[0] * d[0] + d[1] * d[1];
+    let t = if len2 == 0.0 { 0.0 } else { (((p[0] - a[0]) * d[0] + (p[1] - a[1]) * d[1]) / len2).clamp(0.0, 1.0) };
+    (p[0] - a[0] - t * d[0]).hypot(p[1] - a[1] - t * d[1])
+}
+
+fn f64_point(p: Point) -> [f64; 2] {
+    [p.position[0] as f64, p.position[1] as f64]
+}
+
+// 贝塞尔曲线在 t 处的点（de Casteljau）
+fn bezier(controls: &[[f64; 2]], t: f64) -> [f64; 2] {
+    let mut level = controls.to_vec();
+    while level.len() > 1 {
+        level = level.windows(2).map(|w| [w[0][0] + (w[1][0] - w[0][0]) * t, w[0][1] + (w[1][1] - w[0][1]) * t]).collect();
+    }
+    level[0]
+}
+
+// 相邻两个顶点之间的曲线到弦的距离不超过 tolerance（留出 f32 舍入的余量）
+fn assert_sagitta(path: &[Point], ts: &[f32], curve: impl Fn(f64) -> [f64; 2], tolerance: f32) {
+    for i in 0..path.len() - 1 {
+        let (a, b) = (f64_point(path[i]), f64_point(path[i + 1]));
+        let (t0, t1) = (ts[i] as f64, if i + 1 == path.len() - 1 { 1.0 } else { ts[i + 1] as f64 });
+        for k in 1..16 {
+            let t = t0 + (t1 - t0) * k as f64 / 16.0;
+            let d = distance_to_chord(curve(t), a, b);
+            assert!(d <= tolerance as f64 + 1e-4, "弦 {} 的弦高 {} 超过 {}", i, d, tolerance);
+        }
+    }
+}
+
+#[test]
+fn arc_chords_stay_within_tolerance() {
+    let (center, radius) = ([50.0, 50.0], 20.0);
+    for &tolerance in &[2.0, 0.5, 0.1, 0.01] {
+        // 整圆：终点与起点重合
+        let circle = CurvePath {
+            start: point([center[0] + radius, center[1]]),
+            segments: vec![Segment::Arc { center: point(center), to: point([center[0] + radius, center[1]]), anti_clockwise: false }],
+        };
+        let (path, origins) = flatten_path(&circle, tolerance);
+        let ts: Vec<f32> = origins.iter().map(|o| o.t).collect();
+        // 顺时针（屏幕坐标系）时角度增大
+        let arc = |t: f64| {
+            let angle = std::f64::consts::TAU * t;
+            [center[0] as f64 + radius as f64 * angle.cos(), center[1] as f64 + radius as f64 * angle.sin()]
+        };
+        assert_sagitta(&path, &ts, arc, tolerance);
+        // 误差越小顶点越多，而且每条弦都用足了误差：顶点数接近下限
+        let lower = std::f32::consts::PI / (1.0 - tolerance / radius).acos();
+        assert!((path.len() - 1) as f32 >= lower.floor());
+        assert!(((path.len() - 1) as f32) <= lower.ceil() + 1.0);
+    }
+}
+
+#[test]
+fn partial_arc_ends_at_its_endpoint() {
+    // 从 (70, 50) 逆时针转90°到 (50, 30)
+    let path = CurvePath {
+        start: point([70.0, 50.0]),
+        segments: vec![Segment::Arc { center: point([50.0, 50.0]), to: point([50.0, 30.0]), anti_clockwise: true }],
+    };
+    let (ring, origins) = flatten_path(&path, 0.05);
+    let last = ring.len() - 2;
+    assert_eq!(ring[last], point([50.0, 30.0]));
+    assert_close(origins[last].t, 1.0);
+    let ts: Vec<f32> = origins.iter().map(|o| o.t).collect();
+    let arc = |t: f64| {
+        let angle = -std::f64::consts::FRAC_PI_2 * t;
+        [50.0 + 20.0 * angle.cos(), 50.0 + 20.0 * angle.sin()]
+    };
+    // 只检查圆弧部分，最后一条是闭合的直线
+    assert_sagitta(&ring[..ring.len() - 1], &ts, arc, 0.05);
+}
+
+#[test]
+fn bezier_chords_stay_within_tolerance() {
+    let start = [0.0f32, 0.0];
+    let quad = [[0.0, 0.0], [50.0, 80.0], [100.0, 0.0]];
+    let cubic = [[100.0, 0.0], [130.0, -60.0], [-30.0, -60.0], [0.0, 0.0]];
+    for &tolerance in &[1.0, 0.1, 0.01] {
+        let path = CurvePath {
+            start: point(start),
+            segments: vec![
+                Segment::QuadBezier { control: point([50.0, 80.0]), to: point([100.0, 0.0]) },
+                Segment::CubicBezier { control1: point([130.0, -60.0]), control2: point([-30.0, -60.0]), to: point(start) },
+            ],
+        };
+        let result = flatten(&vec![path], tolerance);
+        let (ring, origins) = (&result.polygen[0], &result.origins[0]);
+        assert_eq!(ring[0], ring[ring.len() - 1]);
+        // 按来源分成两段，每段的参数从0开始
+        let split = origins.iter().position(|o| o.segment == 1).unwrap();
+                let mut quad_ts: Vec<f32> = origins[..split].iter().map(|o| o.t).collect();
+        quad_ts[0] = 0.0;
+        let quad_controls: Vec<[f64; 2]> = quad.iter().map(|p| [p[0], p[1]]).collect();
+        assert_sagitta(&ring[..split], &quad_ts, |t| bezier(&quad_controls, t), tolerance);
+
+                let mut cubic_ts: Vec<f32> = origins[split - 1..].iter().map(|o| o.t).collect();
+        cubic_ts[0] = 0.0;
+        let cubic_controls: Vec<[f64; 2]> = cubic.iter().map(|p| [p[0], p[1]]).collect();
+        assert_sagitta(&ring[split - 1..], &cubic_ts, |t| bezier(&cubic_controls, t), tolerance);
+    }
+}
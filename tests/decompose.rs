@@ -0,0 +1,79 @@
+use cg::decompose::{convex_decomposition, triangulate};
+use cg::measure::{path_centroid, path_signed_area, polygen_area};
+use cg::query::{contains, Location};
+use cg::{Path, Polygen};
+
+mod common;
+use common::closed;
+
+// 外环逆时针、内环顺时针（屏幕坐标系，y轴向下）
+fn polygon_with_holes() -> Polygen {
+    vec![
+        closed(&[[0.0, 0.0], [0.0, 60.0], [30.0, 40.0], [60.0, 60.0], [80.0, 20.0], [50.0, 0.0], [40.0, 15.0]]),
+        closed(&[[10.0, 20.0], [20.0, 20.0], [20.0, 30.0], [10.0, 30.0]]),
+        closed(&[[50.0, 20.0], [60.0, 25.0], [55.0, 35.0]]),
+    ]
+}
+
+fn comb() -> Polygen {
+    vec![closed(&[
+        [0.0, 0.0], [0.0, 40.0], [70.0, 40.0], [70.0, 0.0], [60.0, 0.0], [60.0, 30.0], [50.0, 30.0], [50.0, 0.0],
+        [40.0, 0.0], [40.0, 30.0], [30.0, 30.0], [30.0, 0.0], [20.0, 0.0], [20.0, 30.0], [10.0, 30.0], [10.0, 0.0],
+    ])]
+}
+
+fn is_convex(path: &Path) -> bool {
+    let n = path.len() - 1;
+    (0..n).all(|i| {
+        let (a, b, c) = (path[i].position, path[(i + 1) % n].position, path[(i + 2) % n].position);
+        (b[1] - a[1]) * (c[0] - a[0]) - (b[0] - a[0]) * (c[1] - a[1]) >= 0.0
+    })
+}
+
+fn assert_tiles(polygen: &Polygen, parts: &[Path]) {
+    let total: f32 = parts.iter().map(path_signed_area).sum();
+    assert!((total - polygen_area(polygen)).abs() < 1e-3, "{} != {}", total, polygen_area(polygen));
+    for part in parts {
+        assert!(path_signed_area(part) > 0.0);
+        assert!(is_convex(part));
+        assert_eq!(contains(polygen, path_centroid(part).unwrap()), Location::Inside);
+    }
+}
+
+#[test]
+fn triangles_tile_polygon_with_holes() {
+    let polygen = polygon_with_holes();
+    let triangles = triangulate(&polygen);
+    // 简单多边形（含h个洞）剖分出的三角形个数为 n + 2h - 2
+    assert_eq!(triangles.len(), 14 + 2 * 2 - 2);
+    assert_tiles(&polygen, &triangles);
+}
+
+#[test]
+fn convex_parts_tile_polygon_with_holes() {
+    let polygen = polygon_with_holes();
+    for optimize in [false, true] {
+        let parts = convex_decomposition(&polygen, optimize);
+        assert!(parts.len() < triangulate(&polygen).len());
+        assert_tiles(&polygen, &parts);
+    }
+}
+
+#[test]
+fn convex_parts_of_comb() {
+    let polygen = comb();
+    for optimize in [false, true] {
+        let parts = convex_decomposition(&polygen, optimize);
+        // Hertel–Mehlhorn 的块数不超过最优解（4个齿 + 1条梁）的4倍
+        assert!(parts.len() >= 5 && parts.len() <= 20);
+        assert_tiles(&polygen, &parts);
+    }
+}
+
+#[test]
+fn convex_polygon_stays_whole() {
+    let polygen = vec![closed(&[[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]])];
+    let parts = convex_decomposition(&polygen, true);
+    assert_eq!(parts.len(), 1);
+    assert_tiles(&polygen, &parts);
+}
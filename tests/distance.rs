@@ -0,0 +1,65 @@
+use cg::distance::{line_distance, point_line_distance, polygen_distance, signed_distance};
+use cg::Point;
+
+mod common;
+use common::{assert_close, hole, rect};
+
+fn at(x: f32, y: f32) -> Point {
+    Point::new([x, y])
+}
+
+#[test]
+fn signed_distance_inside_outside_and_in_a_hole() {
+    let polygen = vec![rect(0.0, 0.0, 10.0, 10.0), hole(4.0, 4.0, 8.0, 8.0)];
+    // 内部为负，最近点在左边上
+    let (d, p) = signed_distance(&polygen, at(1.0, 5.0)).unwrap();
+    assert_close(d, -1.0);
+    assert_eq!(p, at(0.0, 5.0));
+    // 外部为正，最近点是右上角
+    let (d, p) = signed_distance(&polygen, at(13.0, 14.0)).unwrap();
+    assert_close(d, 5.0);
+    assert_eq!(p, at(10.0, 10.0));
+    // 洞里在多边形外部，距离为正，最近点在洞的边上
+    let (d, p) = signed_distance(&polygen, at(6.0, 5.0)).unwrap();
+    assert_close(d, 1.0);
+    assert_eq!(p, at(6.0, 4.0));
+    // 边界上为0
+    let (d, _) = signed_distance(&polygen, at(10.0, 3.0)).unwrap();
+    assert_close(d, 0.0);
+    assert!(signed_distance(&Vec::new(), at(0.0, 0.0)).is_none());
+}
+
+#[test]
+fn distance_between_polygens() {
+    let a = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    // 右边相隔3，上下错开
+    let (d, pa, pb) = polygen_distance(&a, &vec![rect(13.0, 5.0, 20.0, 20.0)]).unwrap();
+    assert_close(d, 3.0);
+    assert_close(pa.position[0], 10.0);
+    assert_close(pb.position[0], 13.0);
+    assert_close(pa.position[1], pb.position[1]);
+    // 斜对角，最近点是两个角
+    let (d, pa, pb) = polygen_distance(&a, &vec![rect(13.0, 14.0, 20.0, 20.0)]).unwrap();
+    assert_close(d, 5.0);
+    assert_eq!((pa, pb), (at(10.0, 10.0), at(13.0, 14.0)));
+    // 边界相交
+    assert_close(polygen_distance(&a, &vec![rect(5.0, 5.0, 20.0, 20.0)]).unwrap().0, 0.0);
+    // 一个在另一个内部
+    assert_close(polygen_distance(&a, &vec![rect(2.0, 2.0, 3.0, 3.0)]).unwrap().0, 0.0);
+    // 在洞里的多边形到外面的距离是到洞边的距离
+    let with_hole = vec![rect(0.0, 0.0, 10.0, 10.0), hole(2.0, 2.0, 8.0, 8.0)];
+    assert_close(polygen_distance(&with_hole, &vec![rect(4.0, 4.0, 6.0, 5.0)]).unwrap().0, 2.0);
+    assert!(polygen_distance(&a, &Vec::new()).is_none());
+}
+
+#[test]
+fn point_and_segment_distances() {
+    let line = [at(0.0, 0.0), at(10.0, 0.0)];
+    assert_eq!(point_line_distance(at(4.0, 3.0), &line), (3.0, at(4.0, 0.0)));
+    assert_eq!(point_line_distance(at(13.0, 4.0), &line), (5.0, at(10.0, 0.0)));
+    let (d, p, q) = line_distance(&line, &[at(5.0, -1.0), at(5.0, 1.0)]);
+    assert_eq!((d, p, q), (0.0, at(5.0, 0.0), at(5.0, 0.0)));
+    let (d, _, q) = line_distance(&line, &[at(12.0, 1.0), at(20.0, 5.0)]);
+    assert_close(d, 5.0f32.sqrt());
+    assert_eq!(q, at(12.0, 1.0));
+}
@@ -0,0 +1,84 @@
+use cg::distance::signed_distance;
+use cg::hatch::{hatch, HatchOptions, HatchPattern};
+use cg::query::{contains, Location};
+use cg::{Path, Point, Polygen};
+
+mod common;
+use common::{hole, rect, ring};
+
+// 凹的 U 形，底部带一个洞，斜边较多
+fn u_shape_with_hole() -> Polygen {
+    vec![
+        ring(&[[0.0, 0.0], [3.0, 0.0], [4.0, 7.0], [6.0, 7.0], [7.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]], true),
+        hole(3.0, 8.0, 7.0, 9.0),
+    ]
+}
+
+fn segments(path: &Path) -> impl Iterator<Item = (Point, Point)> + '_ {
+    path.windows(2).map(|w| (w[0], w[1]))
+}
+
+// 沿线段取若干点，都不在多边形外部；落在斜边上的端点舍入到 f32 后可能在边界外侧极近处
+fn assert_segment_inside(polygen: &Polygen, a: Point, b: Point) {
+    let (a, b) = (a.position, b.position);
+    for k in 0..=16 {
+        let t = k as f32 / 16.0;
+        let p = Point::new([a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]);
+        let outside = contains(polygen, p) == Location::Outside && signed_distance(polygen, p).unwrap().0 > 1e-5;
+        assert!(!outside, "{:?} -> {:?} 在 {:?} 处离开多边形", a, b, p.position);
+    }
+}
+
+fn length(a: Point, b: Point) -> f32 {
+    (b.position[0] - a.position[0]).hypot(b.position[1] - a.position[1])
+}
+
+#[test]
+fn every_emitted_segment_lies_inside() {
+    let polygen = u_shape_with_hole();
+    for &pattern in &[HatchPattern::Parallel, HatchPattern::CrossHatch, HatchPattern::ZigZag] {
+        for &angle in &[0.0f32, 0.3, 1.0, std::f32::consts::FRAC_PI_4, std::f32::consts::FRAC_PI_2, 2.5] {
+            let options = HatchOptions { angle, spacing: 0.45, pattern };
+            let paths = hatch(&polygen, &options);
+            assert!(!paths.is_empty());
+            for path in &paths {
+                assert!(path.len() >= 2);
+                for (a, b) in segments(path) {
+                    assert_segment_inside(&polygen, a, b);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn zigzag_connectors_are_short() {
+    let polygen = u_shape_with_hole();
+    let spacing = 0.45;
+    for &angle in &[0.0f32, 0.3, 1.0, std::f32::consts::FRAC_PI_2] {
+        let paths = hatch(&polygen, &HatchOptions { angle, spacing, pattern: HatchPattern::ZigZag });
+        let direction = [angle.cos(), angle.sin()];
+        for path in &paths {
+            for (a, b) in segments(path) {
+                // 不沿填充方向的是连线
+                let d = [b.position[0] - a.position[0], b.position[1] - a.position[1]];
+                let across = (d[0] * direction[1] - d[1] * direction[0]).abs();
+                if across > 1e-3 {
+                    assert!(length(a, b) <= 2.0 * spacing + 1e-4, "连线 {:?} -> {:?} 过长", a.position, b.position);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn zigzag_of_a_rectangle_is_one_path() {
+    let polygen = vec![rect(0.0, 0.0, 10.0, 4.0)];
+    let paths = hatch(&polygen, &HatchOptions { angle: 0.0, spacing: 1.0, pattern: HatchPattern::ZigZag });
+    // y = 0..4 共五条线，首尾相接成一条折线
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].len(), 10);
+    for (a, b) in segments(&paths[0]) {
+        assert_segment_inside(&polygen, a, b);
+    }
+}
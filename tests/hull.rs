@@ -0,0 +1,62 @@
+use cg::hull::{convex_hull, polygen_convex_hull};
+use cg::measure::path_signed_area;
+use cg::query::{contains, Location};
+use cg::{Path, Point};
+
+mod common;
+use common::{assert_close, hole, polyline, rect};
+
+// 闭合、逆时针，每个输入点都不在凸包外部
+fn assert_hull(points: &[Point], hull: &Path) {
+    assert_eq!(hull[0], hull[hull.len() - 1]);
+    assert!(path_signed_area(hull) > 0.0);
+    let polygen = vec![hull.clone()];
+    for &p in points {
+        assert_ne!(contains(&polygen, p), Location::Outside, "{:?}", p);
+    }
+}
+
+#[test]
+fn collinear_points_on_hull_edges_are_dropped() {
+    // 正方形每条边的中点、中心以及一个内部点
+    let points = polyline(&[
+        [0.0, 0.0], [5.0, 0.0], [10.0, 0.0], [10.0, 5.0], [10.0, 10.0], [5.0, 10.0], [0.0, 10.0], [0.0, 5.0], [5.0, 5.0], [2.0, 7.0],
+    ]);
+    let hull = convex_hull(&points);
+    assert_eq!(hull.len(), 5);
+    assert_close(path_signed_area(&hull), 100.0);
+    assert_hull(&points, &hull);
+}
+
+#[test]
+fn duplicate_points_count_once() {
+    let mut points = polyline(&[[0.0, 0.0], [4.0, 0.0], [2.0, 3.0], [1.0, 1.0]]);
+    points.extend(points.clone());
+    points.push(Point::new([4.0, 0.0]));
+    let hull = convex_hull(&points);
+    assert_eq!(hull.len(), 4);
+    assert_close(path_signed_area(&hull), 6.0);
+    assert_hull(&points, &hull);
+    // 顺序不影响结果
+    points.reverse();
+    assert_eq!(convex_hull(&points), hull);
+}
+
+#[test]
+fn degenerate_inputs() {
+    assert!(convex_hull(&[]).is_empty());
+    let p = Point::new([1.0, 2.0]);
+    assert_eq!(convex_hull(&[p, p, p]), vec![p, p]);
+    // 全部共线时是线段的两个端点
+    let points = polyline(&[[2.0, 2.0], [0.0, 0.0], [3.0, 3.0], [1.0, 1.0], [3.0, 3.0]]);
+    assert_eq!(convex_hull(&points), polyline(&[[0.0, 0.0], [3.0, 3.0], [0.0, 0.0]]));
+}
+
+#[test]
+fn hull_of_a_polygen_with_a_hole() {
+    let polygen = vec![rect(0.0, 0.0, 10.0, 4.0), hole(2.0, 1.0, 8.0, 3.0)];
+    let hull = polygen_convex_hull(&polygen);
+    assert_close(path_signed_area(&hull), 40.0);
+    let points: Vec<Point> = polygen.iter().flatten().copied().collect();
+    assert_hull(&points, &hull);
+}
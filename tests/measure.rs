@@ -0,0 +1,36 @@
+use cg::measure::{polygen_central_moments, polygen_second_moments};
+
+mod common;
+use common::{assert_close, hole, rect};
+
+#[test]
+fn central_moments_do_not_depend_on_position() {
+    // 10 x 10 的正方形，ixx = iyy = 10^4 / 12，ixy = 0
+    for &offset in &[0.0, 1000.0, 5000.0, -20000.0] {
+        let square = vec![rect(offset, offset, offset + 10.0, offset + 10.0)];
+        let m = polygen_central_moments(&square).unwrap();
+        assert_close(m.ixx, 10000.0 / 12.0);
+        assert_close(m.iyy, 10000.0 / 12.0);
+        assert_close(m.ixy, 0.0);
+    }
+}
+
+#[test]
+fn central_moments_of_a_square_with_a_hole() {
+    // 10 x 10 挖掉中间 4 x 4，离原点很远
+    let (x, y) = (3000.0, -4000.0);
+    let polygen = vec![rect(x, y, x + 10.0, y + 10.0), hole(x + 3.0, y + 3.0, x + 7.0, y + 7.0)];
+    let m = polygen_central_moments(&polygen).unwrap();
+    assert_close(m.ixx, (10000.0 - 256.0) / 12.0);
+    assert_close(m.iyy, (10000.0 - 256.0) / 12.0);
+    assert_close(m.ixy, 0.0);
+}
+
+#[test]
+fn second_moments_about_the_origin() {
+    // 0..2 x 0..3：ixx = b h^3 / 3，iyy = h b^3 / 3，ixy = b^2 h^2 / 4
+    let m = polygen_second_moments(&vec![rect(0.0, 0.0, 2.0, 3.0)]);
+    assert_close(m.ixx, 18.0);
+    assert_close(m.iyy, 8.0);
+    assert_close(m.ixy, 9.0);
+}
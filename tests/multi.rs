@@ -0,0 +1,110 @@
+use cg::boolean::BooleanOp;
+use cg::measure::{polygen_area, polygen_signed_area};
+use cg::multi::{boolean_sets, split_polygens};
+use cg::valid::validate;
+use cg::Polygen;
+
+mod common;
+use common::{assert_close, hole, rect};
+
+fn total_area(polygens: &[Polygen]) -> f32 {
+    polygens.iter().map(polygen_signed_area).sum()
+}
+
+fn assert_valid(polygens: &[Polygen]) {
+    for polygen in polygens {
+        assert_eq!(validate(polygen), Vec::new());
+    }
+}
+
+#[test]
+fn adjacent_parcels_against_a_zone() {
+    // 两个共边的地块，分区与左边的地块共三条边
+    let parcels = vec![vec![rect(0.0, 0.0, 10.0, 10.0)], vec![rect(10.0, 0.0, 20.0, 10.0)]];
+    let zones = vec![vec![rect(0.0, 0.0, 10.0, 20.0)]];
+
+    let intersection = boolean_sets(&parcels, &zones, BooleanOp::Intersection);
+    assert_eq!(intersection.len(), 1);
+    assert_close(total_area(&intersection), 100.0);
+
+    let difference = boolean_sets(&parcels, &zones, BooleanOp::Difference);
+    assert_eq!(difference.len(), 1);
+    assert_close(total_area(&difference), 100.0);
+
+    let union = boolean_sets(&parcels, &zones, BooleanOp::Union);
+    assert_valid(&union);
+    assert_eq!(union.len(), 1);
+    assert_close(total_area(&union), 300.0);
+}
+
+#[test]
+fn grid_layer_against_a_window() {
+    // 2 x 2 的网格，窗口盖住中间的公共顶点
+    let cells: Vec<Polygen> = (0..4).map(|k| {
+        let (x, y) = (10.0 * (k % 2) as f32, 10.0 * (k / 2) as f32);
+        vec![rect(x, y, x + 10.0, y + 10.0)]
+    }).collect();
+    let window = vec![vec![rect(5.0, 5.0, 15.0, 15.0)]];
+
+    let intersection = boolean_sets(&cells, &window, BooleanOp::Intersection);
+    assert_valid(&intersection);
+    assert_eq!(intersection.len(), 4);
+    for polygen in &intersection { assert_close(polygen_signed_area(polygen), 25.0); }
+
+    let difference = boolean_sets(&cells, &window, BooleanOp::Difference);
+    assert_valid(&difference);
+    assert_eq!(difference.len(), 4);
+    for polygen in &difference { assert_close(polygen_signed_area(polygen), 75.0); }
+
+    let union = boolean_sets(&cells, &window, BooleanOp::Union);
+    assert_eq!(union.len(), 1);
+    assert_close(total_area(&union), 400.0);
+}
+
+#[test]
+fn overlapping_clip_polygons_are_not_counted_twice() {
+    let subjects = vec![vec![rect(0.0, 0.0, 20.0, 10.0)]];
+    // 两个裁剪多边形重叠 4 x 5，另有一个完全重复的
+    let clips = vec![vec![rect(0.0, 0.0, 12.0, 10.0)], vec![rect(8.0, 0.0, 20.0, 5.0)], vec![rect(0.0, 0.0, 12.0, 10.0)]];
+
+    let intersection = boolean_sets(&subjects, &clips, BooleanOp::Intersection);
+    assert_valid(&intersection);
+    assert_eq!(intersection.len(), 1);
+    assert_close(total_area(&intersection), 160.0);
+
+    let difference = boolean_sets(&subjects, &clips, BooleanOp::Difference);
+    assert_eq!(difference.len(), 1);
+    assert_close(total_area(&difference), 40.0);
+
+    let union = boolean_sets(&subjects, &clips, BooleanOp::Union);
+    assert_eq!(union.len(), 1);
+    assert_close(total_area(&union), 200.0);
+}
+
+#[test]
+fn subjects_away_from_every_clip() {
+    let subjects = vec![vec![rect(0.0, 0.0, 10.0, 10.0)], vec![rect(50.0, 0.0, 60.0, 10.0)]];
+    let clips = vec![vec![rect(5.0, 0.0, 15.0, 10.0)]];
+    assert_close(total_area(&boolean_sets(&subjects, &clips, BooleanOp::Intersection)), 50.0);
+    let difference = boolean_sets(&subjects, &clips, BooleanOp::Difference);
+    assert_eq!(difference.len(), 2);
+    assert_close(total_area(&difference), 150.0);
+}
+
+#[test]
+fn split_polygens_by_nesting() {
+    // 外环带一个洞，洞里有一个岛，岛上又有一个洞；最后一个洞不在任何外环里
+    let rings = vec![
+        rect(10.0, 10.0, 20.0, 20.0),
+        hole(5.0, 5.0, 25.0, 25.0),
+        hole(13.0, 13.0, 17.0, 17.0),
+        rect(0.0, 0.0, 30.0, 30.0),
+        hole(40.0, 40.0, 45.0, 45.0),
+    ];
+    let polygens = split_polygens(&rings);
+    assert_eq!(polygens.len(), 2);
+    assert_eq!(polygens[0], vec![rings[0].clone(), rings[2].clone()]);
+    assert_eq!(polygens[1], vec![rings[3].clone(), rings[1].clone()]);
+    assert_close(polygen_area(&polygens[0]), 100.0 - 16.0);
+    assert_close(polygen_area(&polygens[1]), 900.0 - 400.0);
+}
@@ -0,0 +1,31 @@
+use cg::measure::polygen_signed_area;
+use cg::nfp::{inner_fit_polygon, no_fit_polygon};
+use cg::valid::validate;
+use cg::Polygen;
+
+mod common;
+use common::{assert_close, rect, ring};
+
+#[test]
+fn no_fit_polygon_of_an_l_shape() {
+    // L 形凸分解后的两块共边，各自与正方形的闵可夫斯基和也共边
+    let l_shape = vec![ring(&[[0.0, 0.0], [10.0, 0.0], [10.0, 4.0], [4.0, 4.0], [4.0, 10.0], [0.0, 10.0]], true)];
+    let part = vec![rect(0.0, 0.0, 2.0, 2.0)];
+    let nfp = no_fit_polygon(&l_shape, &part);
+    assert_eq!(validate(&nfp), Vec::new());
+    assert_eq!(nfp.len(), 1);
+    // 每条臂向外加宽2：12 x 6 与 6 x 12 的 L 形
+    assert_close(polygen_signed_area(&nfp), 12.0 * 6.0 + 6.0 * 6.0);
+}
+
+#[test]
+fn inner_fit_polygon_of_a_frame() {
+    let frame: Polygen = vec![rect(0.0, 0.0, 20.0, 20.0), ring(&[[5.0, 5.0], [15.0, 5.0], [15.0, 15.0], [5.0, 15.0]], false)];
+    let part = vec![rect(0.0, 0.0, 2.0, 2.0)];
+    let ifp = inner_fit_polygon(&frame, &part);
+    assert_eq!(validate(&ifp), Vec::new());
+    assert_eq!(ifp.len(), 2);
+    // 参考点可达 0..18 减去 3..15
+    assert_close(polygen_signed_area(&ifp), 18.0 * 18.0 - 12.0 * 12.0);
+    assert!(inner_fit_polygon(&part, &frame).is_empty());
+}
@@ -0,0 +1,89 @@
+use cg::pocket::{linked_toolpaths, pocket, PocketContour};
+use cg::query::{contains, Location};
+
+mod common;
+use common::{hole, rect, ring};
+
+// 最长的一条父子链有几层
+fn depth(contour: &PocketContour) -> usize {
+    1 + contour.children.iter().map(depth).max().unwrap_or(0)
+}
+
+// 每个子区域比父区域多缩进一个步距，外环落在父区域内
+fn assert_links(contour: &PocketContour, stepover: f32) {
+    for child in &contour.children {
+        assert!((child.distance - contour.distance - stepover).abs() < 1e-4, "{} -> {}", contour.distance, child.distance);
+        for p in &child.polygen[0] {
+            assert_ne!(contains(&contour.polygen, *p), Location::Outside, "{:?} 不在父区域内", p.position);
+        }
+        assert_links(child, stepover);
+    }
+}
+
+#[test]
+fn square_pocket_is_a_single_chain() {
+    let square = vec![rect(0.0, 0.0, 20.0, 20.0)];
+    let tree = pocket(&square, 1.0, 2.0);
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0].distance, 1.0);
+    // 距离 1、3、5、7、9，每层一个区域
+    assert_eq!(depth(&tree[0]), 5);
+    let mut cur = &tree[0];
+    while let Some(child) = cur.children.first() {
+        assert_eq!(cur.children.len(), 1);
+        cur = child;
+    }
+    assert_links(&tree[0], 2.0);
+}
+
+#[test]
+fn narrow_neck_splits_into_two_children() {
+    // 两个方块由宽度为2的窄道相连，缩进超过1后窄道消失
+    let dumbbell = vec![ring(&[
+        [0.0, 0.0], [10.0, 0.0], [10.0, 4.0], [14.0, 4.0], [14.0, 0.0], [24.0, 0.0],
+        [24.0, 10.0], [14.0, 10.0], [14.0, 6.0], [10.0, 6.0], [10.0, 10.0], [0.0, 10.0],
+    ], true)];
+    let tree = pocket(&dumbbell, 0.5, 1.0);
+    assert_eq!(tree.len(), 1);
+    let root = &tree[0];
+    assert_eq!(root.children.len(), 2);
+    for child in &root.children {
+        assert_eq!(child.distance, 1.5);
+        // 1.5、2.5、3.5、4.5 共四层
+        assert_eq!(depth(child), 4);
+    }
+    // 两个子区域分别在左右两个方块里
+    let sides: Vec<bool> = root.children.iter().map(|c| c.polygen[0].iter().all(|p| p.position[0] < 12.0)).collect();
+    assert!(sides.contains(&true) && sides.contains(&false));
+    assert_links(root, 1.0);
+}
+
+#[test]
+fn island_is_carried_as_a_hole_down_the_tree() {
+    let with_island = vec![rect(0.0, 0.0, 20.0, 20.0), hole(8.0, 8.0, 12.0, 12.0)];
+    let tree = pocket(&with_island, 1.0, 1.0);
+    assert_eq!(tree.len(), 1);
+    // 岛屿边界向外扩，外环向内缩，各层都是带一个洞的环形，直到两者在距离4左右相遇
+    let mut cur = &tree[0];
+    let mut levels = 1;
+    assert_eq!(cur.polygen.len(), 2);
+    while let Some(child) = cur.children.first() {
+        assert_eq!(cur.children.len(), 1);
+        assert_eq!(child.polygen.len(), 2);
+        cur = child;
+        levels += 1;
+    }
+    assert_eq!(levels, depth(&tree[0]));
+    assert!(cur.distance < 4.0);
+    assert_links(&tree[0], 1.0);
+
+    // 刀具路径经过每一层的外环和洞上的所有顶点
+    let paths = linked_toolpaths(&tree);
+    let mut cur = Some(&tree[0]);
+    while let Some(contour) = cur {
+        for p in contour.polygen.iter().flatten() {
+            assert!(paths.iter().flatten().any(|q| q == p), "{:?} 不在刀具路径上", p.position);
+        }
+        cur = contour.children.first();
+    }
+}
@@ -0,0 +1,95 @@
+use cg::polyline::{clip_polyline, PolylineClipResult, PolylinePiece};
+use cg::Polygen;
+
+mod common;
+use common::{assert_close, closed, hole, polyline, rect};
+
+fn length(pieces: &[PolylinePiece]) -> f32 {
+    pieces.iter().flat_map(|piece| piece.path.windows(2))
+        .map(|w| (w[1].position[0] - w[0].position[0]).hypot(w[1].position[1] - w[0].position[1]))
+        .sum()
+}
+
+// 参数在折线范围内，首尾相接，没有退化的碎段
+fn assert_well_formed(result: &PolylineClipResult, segments: usize) {
+    let mut pieces: Vec<&PolylinePiece> = result.inside.iter().chain(&result.outside).collect();
+    pieces.sort_by(|a, b| a.start.total_cmp(&b.start));
+    assert_eq!(pieces[0].start, 0.0);
+    assert_eq!(pieces[pieces.len() - 1].end, segments as f32);
+    for w in pieces.windows(2) { assert_eq!(w[0].end, w[1].start); }
+    for piece in &pieces {
+        assert!(piece.start < piece.end);
+        assert!(piece.path.windows(2).all(|w| w[0] != w[1]), "{:?}", piece.path);
+    }
+}
+
+// 斜边从 (0.1, 0.3) 到 (7.3, 3.1)，坐标不能精确表示，斜边上的点舍入后不严格共线
+fn triangle() -> Polygen {
+    vec![closed(&[[0.1, 0.3], [7.3, 3.1], [0.1, 3.1]])]
+}
+
+#[test]
+fn line_starting_or_ending_on_the_boundary() {
+    let polygen = triangle();
+    for k in 1..20 {
+        let t = k as f32 / 20.0;
+        let on_edge = [0.1 + 7.2 * t, 0.3 + 2.8 * t];
+        for &far in &[[on_edge[0], 10.0], [on_edge[0], -10.0], [-5.0, 20.0], [20.0, -3.0]] {
+            let line = polyline(&[on_edge, far]);
+            let result = clip_polyline(&line, &polygen);
+            assert_well_formed(&result, 1);
+            let mut reversed = line.clone();
+            reversed.reverse();
+            let back = clip_polyline(&reversed, &polygen);
+            assert_well_formed(&back, 1);
+            assert_eq!(result.inside.len(), back.inside.len());
+            assert_close(length(&result.inside), length(&back.inside));
+        }
+    }
+}
+
+#[test]
+fn overlap_with_an_edge_is_inside_in_both_directions() {
+    let square = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    // 沿下边从外面进来再出去
+    for line in &[polyline(&[[-5.0, 0.0], [15.0, 0.0]]), polyline(&[[15.0, 0.0], [-5.0, 0.0]])] {
+        let result = clip_polyline(line, &square);
+        assert_well_formed(&result, 1);
+        assert_eq!(result.inside.len(), 1);
+        assert_close(length(&result.inside), 10.0);
+        assert_eq!(result.outside.len(), 2);
+    }
+
+    // 沿斜边的一段，端点舍入后略微离开斜边
+    let polygen = triangle();
+    let along = polyline(&[[3.7, 1.7], [0.099999905, 0.29999995]]);
+    for line in &[along.clone(), along.into_iter().rev().collect()] {
+        let result = clip_polyline(line, &polygen);
+        assert_well_formed(&result, 1);
+        assert!(result.outside.is_empty());
+        assert_close(length(&result.inside), 3.6f32.hypot(1.4));
+    }
+
+    // 沿斜边延长出去
+    for line in &[polyline(&[[10.9, 4.5], [3.7, 1.7]]), polyline(&[[3.7, 1.7], [10.9, 4.5]])] {
+        let result = clip_polyline(line, &polygen);
+        assert_well_formed(&result, 1);
+        assert_eq!(result.inside.len(), 1);
+        assert_eq!(result.outside.len(), 1);
+        assert_close(length(&result.inside), 3.6f32.hypot(1.4));
+    }
+}
+
+#[test]
+fn pieces_of_a_polyline_crossing_a_hole() {
+    let polygen = vec![rect(0.0, 0.0, 10.0, 10.0), hole(3.0, 3.0, 7.0, 7.0)];
+    let line = polyline(&[[-1.0, 5.0], [5.0, 5.0], [5.0, 11.0]]);
+    let result = clip_polyline(&line, &polygen);
+    assert_well_formed(&result, 2);
+    // 进入多边形，进洞，出洞，离开多边形
+    assert_eq!(result.inside.len(), 2);
+    assert_eq!(result.outside.len(), 3);
+    assert_close(length(&result.inside), 3.0 + 3.0);
+    assert_close(result.inside[0].start, 1.0 / 6.0);
+    assert_close(result.inside[0].end, 4.0 / 6.0);
+}
@@ -0,0 +1,71 @@
+use cg::prepared::PreparedPolygen;
+use cg::query::{contains, Location};
+use cg::{Point, Polygen};
+
+mod common;
+use common::{hole, ring};
+
+// 线性同余生成器，[0, 1) 上的伪随机数
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+// 不规则的星形外环，中间一个矩形洞，另有一个岛
+fn star_with_hole(random: &mut Random) -> Polygen {
+    let n = 40;
+    let shell: Vec<[f32; 2]> = (0..n).map(|k| {
+        let angle = k as f32 / n as f32 * std::f32::consts::TAU;
+        let r = if k % 2 == 0 { 80.0 } else { 40.0 + 30.0 * random.next() };
+        [100.0 + r * angle.cos(), 100.0 + r * angle.sin()]
+    }).collect();
+    vec![ring(&shell, true), hole(85.0, 85.0, 115.0, 115.0), ring(&[[95.0, 95.0], [105.0, 95.0], [100.0, 105.0]], true)]
+}
+
+fn assert_agrees(polygen: &Polygen, prepared: &PreparedPolygen, points: &[Point]) {
+    for &p in points {
+        assert_eq!(prepared.contains(p), contains(polygen, p), "{:?}", p);
+    }
+}
+
+#[test]
+fn agrees_with_query_on_random_points() {
+    let mut random = Random(17);
+    let polygen = star_with_hole(&mut random);
+    let prepared = PreparedPolygen::new(&polygen);
+    let points: Vec<Point> = (0..20000).map(|_| Point::new([random.next() * 220.0 - 10.0, random.next() * 220.0 - 10.0])).collect();
+    assert_agrees(&polygen, &prepared, &points);
+    let locations = prepared.contains_all(&points);
+    assert!(locations.contains(&Location::Inside) && locations.contains(&Location::Outside));
+}
+
+#[test]
+fn agrees_with_query_on_the_boundary() {
+    let mut random = Random(5);
+    let polygen = star_with_hole(&mut random);
+    let prepared = PreparedPolygen::new(&polygen);
+    // 所有顶点，洞的边上的点，以及与顶点同一水平线、竖直线的点
+    let mut points: Vec<Point> = polygen.iter().flatten().copied().collect();
+    for k in 0..=30 {
+        let t = 85.0 + k as f32;
+        points.extend([[t, 85.0], [t, 115.0], [85.0, t], [115.0, t]].iter().map(|&p| Point::new(p)));
+    }
+    for v in polygen.iter().flatten() {
+        for &dx in &[-0.5, 0.5, 7.0] {
+            points.push(Point::new([v.position[0] + dx, v.position[1]]));
+            points.push(Point::new([v.position[0], v.position[1] + dx]));
+        }
+    }
+    assert_agrees(&polygen, &prepared, &points);
+    assert!(polygen.iter().flatten().all(|&v| prepared.contains(v) == Location::OnBoundary));
+}
+
+#[test]
+fn empty_polygen_contains_nothing() {
+    let prepared = PreparedPolygen::new(&Vec::new());
+    assert_eq!(prepared.contains(Point::new([0.0, 0.0])), Location::Outside);
+}
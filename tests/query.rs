@@ -0,0 +1,63 @@
+use cg::query::{contains, winding_number, Location};
+use cg::{Point, Polygen};
+
+mod common;
+use common::{closed, hole, rect};
+
+// 10 x 10 的正方形，中间 4 x 4 的洞；左上角还有一个斜边
+fn square_with_hole() -> Polygen {
+    vec![
+        closed(&[[2.0, 0.0], [0.0, 2.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]]),
+        hole(3.0, 3.0, 7.0, 7.0),
+    ]
+}
+
+fn at(x: f32, y: f32) -> Point {
+    Point::new([x, y])
+}
+
+#[test]
+fn points_on_edges_and_vertices() {
+    let polygen = square_with_hole();
+    for &p in &[[5.0, 0.0], [10.0, 5.0], [0.0, 6.0], [1.0, 1.0], [5.0, 3.0], [7.0, 4.5]] {
+        assert_eq!(contains(&polygen, at(p[0], p[1])), Location::OnBoundary, "{:?}", p);
+    }
+    for &p in &[[2.0, 0.0], [0.0, 2.0], [10.0, 10.0], [3.0, 3.0], [7.0, 7.0]] {
+        assert_eq!(contains(&polygen, at(p[0], p[1])), Location::OnBoundary, "{:?}", p);
+    }
+}
+
+#[test]
+fn points_inside_holes_are_outside() {
+    let polygen = square_with_hole();
+    assert_eq!(contains(&polygen, at(5.0, 5.0)), Location::Outside);
+    assert_eq!(winding_number(&polygen, at(5.0, 5.0)), 0);
+    // 与洞的顶点在同一水平线、同一竖直线上
+    assert_eq!(contains(&polygen, at(5.0, 3.5)), Location::Outside);
+    assert_eq!(contains(&polygen, at(1.0, 3.0)), Location::Inside);
+    assert_eq!(contains(&polygen, at(3.0, 9.0)), Location::Inside);
+    assert_eq!(winding_number(&polygen, at(1.0, 3.0)), 1);
+}
+
+#[test]
+fn points_near_the_boundary() {
+    let polygen = square_with_hole();
+    // 斜边两侧
+    assert_eq!(contains(&polygen, at(0.9, 0.9)), Location::Outside);
+    assert_eq!(contains(&polygen, at(1.1, 1.1)), Location::Inside);
+    // 边的延长线上
+    assert_eq!(contains(&polygen, at(11.0, 0.0)), Location::Outside);
+    assert_eq!(contains(&polygen, at(0.0, -1.0)), Location::Outside);
+    assert_eq!(contains(&polygen, at(5.0, -0.001)), Location::Outside);
+    assert_eq!(contains(&polygen, at(5.0, 0.001)), Location::Inside);
+}
+
+#[test]
+fn orientation_sets_the_sign_of_the_winding_number() {
+    let square = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    assert_eq!(winding_number(&square, at(5.0, 5.0)), 1);
+    let clockwise = vec![hole(0.0, 0.0, 10.0, 10.0)];
+    assert_eq!(winding_number(&clockwise, at(5.0, 5.0)), -1);
+    assert_eq!(contains(&clockwise, at(5.0, 5.0)), Location::Inside);
+    assert_eq!(contains(&Vec::new(), at(0.0, 0.0)), Location::Outside);
+}
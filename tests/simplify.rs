@@ -0,0 +1,69 @@
+use cg::measure::path_signed_area;
+use cg::simplify::{simplify, SimplifyAlgorithm};
+use cg::valid::{is_valid, validate, ValidityIssue};
+use cg::Polygen;
+
+mod common;
+use common::ring;
+
+fn vertex_count(polygen: &Polygen) -> usize {
+    polygen.iter().map(|path| path.len() - 1).sum()
+}
+
+// 上边向外凸起一点，一个窄槽从下边伸上来，槽尖越过了上边两端点的连线；
+// 左边的 (1, 50) 几乎共线，任何情况下都可以删掉
+fn notch_under_bump() -> Polygen {
+    vec![ring(&[
+        [0.0, 0.0], [50.0, -4.0], [100.0, 0.0], [100.0, 100.0], [54.0, 100.0], [50.0, -2.0], [46.0, 100.0], [0.0, 100.0], [1.0, 50.0],
+    ], true)]
+}
+
+// 右边有一个小凸起，洞就在凸起里面
+fn hole_in_bump() -> Polygen {
+    vec![
+        ring(&[[0.0, 0.0], [100.0, 0.0], [100.0, 45.0], [104.0, 50.0], [100.0, 55.0], [100.0, 100.0], [0.0, 100.0]], true),
+        ring(&[[100.5, 49.0], [102.0, 49.0], [102.0, 51.0], [100.5, 51.0]], false),
+    ]
+}
+
+#[test]
+fn removing_the_bump_would_cut_across_the_notch() {
+    let polygen = notch_under_bump();
+    assert!(is_valid(&polygen));
+    for &(algorithm, tolerance) in &[(SimplifyAlgorithm::DouglasPeucker, 5.0), (SimplifyAlgorithm::VisvalingamWhyatt, 250.0)] {
+        let loose = simplify(&polygen, algorithm, tolerance, false);
+        assert!(validate(&loose).iter().any(|issue| matches!(issue, ValidityIssue::Intersection { .. })), "{:?}", algorithm);
+
+        let kept = simplify(&polygen, algorithm, tolerance, true);
+        assert_eq!(validate(&kept), Vec::new(), "{:?}", algorithm);
+        assert!(vertex_count(&kept) < vertex_count(&polygen), "{:?}", algorithm);
+    }
+}
+
+#[test]
+fn hole_stays_inside_its_shell() {
+    let polygen = hole_in_bump();
+    assert!(is_valid(&polygen));
+    let loose = simplify(&polygen, SimplifyAlgorithm::DouglasPeucker, 10.0, false);
+    assert!(!is_valid(&loose));
+
+    let kept = simplify(&polygen, SimplifyAlgorithm::DouglasPeucker, 10.0, true);
+    assert_eq!(validate(&kept), Vec::new());
+    assert_eq!(kept.len(), 2);
+    assert!(path_signed_area(&kept[1]) < 0.0);
+    assert!(vertex_count(&kept) < vertex_count(&polygen));
+}
+
+#[test]
+fn small_holes_are_not_collapsed() {
+    // 洞的面积远小于 VW 的阈值，但化简后仍至少是一个三角形
+    let polygen = vec![
+        ring(&[[0.0, 0.0], [100.0, 0.0], [100.0, 100.0], [0.0, 100.0]], true),
+        ring(&[[40.0, 40.0], [43.0, 40.0], [43.0, 41.0], [41.5, 42.0], [40.0, 41.0]], false),
+    ];
+    let kept = simplify(&polygen, SimplifyAlgorithm::VisvalingamWhyatt, 50.0, true);
+    assert_eq!(validate(&kept), Vec::new());
+    assert_eq!(kept.len(), 2);
+    assert!(kept[1].len() >= 4);
+    assert!(path_signed_area(&kept[1]) < 0.0);
+}
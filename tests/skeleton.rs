@@ -0,0 +1,66 @@
+use cg::measure::{path_signed_area, polygen_signed_area};
+use cg::skeleton::{skeleton_inset, skeleton_insets, straight_skeleton};
+use cg::valid::validate;
+use cg::Polygen;
+
+mod common;
+use common::{assert_close, ring};
+
+// 两臂宽4、长10的 L 形，面积64，(4, 4) 是凹顶点
+fn l_shape() -> Polygen {
+    vec![ring(&[[0.0, 0.0], [10.0, 0.0], [10.0, 4.0], [4.0, 4.0], [4.0, 10.0], [0.0, 10.0]], true)]
+}
+
+// 10 x 10 的正方形中间挖掉 6 x 6，面积64，环宽2
+fn square_with_hole() -> Polygen {
+    vec![
+        ring(&[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]], true),
+        ring(&[[2.0, 2.0], [8.0, 2.0], [8.0, 8.0], [2.0, 8.0]], false),
+    ]
+}
+
+// 每条输入边一个面，面都是逆时针的，面积之和等于多边形的面积
+fn assert_faces_cover(polygen: &Polygen) {
+    let skeleton = straight_skeleton(polygen);
+    let edges: usize = polygen.iter().map(|path| path.len() - 1).sum();
+    assert_eq!(skeleton.faces.len(), edges);
+    for face in &skeleton.faces {
+        assert!(path_signed_area(&face.path) > 0.0, "{:?}", face.edge);
+    }
+    let total: f32 = skeleton.faces.iter().map(|face| path_signed_area(&face.path)).sum();
+    assert_close(total, polygen_signed_area(polygen));
+    for node in &skeleton.nodes {
+        assert!(node.height >= 0.0);
+    }
+}
+
+#[test]
+fn faces_of_an_l_shape() {
+    assert_faces_cover(&l_shape());
+}
+
+#[test]
+fn faces_of_a_square_with_a_hole() {
+    assert_faces_cover(&square_with_hole());
+}
+
+#[test]
+fn insets_of_an_l_shape() {
+    // 凹角处内缩后仍是尖角，两臂宽 4 - 2d
+    let insets = skeleton_insets(&l_shape(), &[1.0, 0.5, 2.5]);
+    assert_eq!(validate(&insets[1]), Vec::new());
+    assert_close(polygen_signed_area(&insets[1]), 9.0 * 3.0 + 3.0 * 6.0);
+    assert_eq!(validate(&insets[0]), Vec::new());
+    assert_close(polygen_signed_area(&insets[0]), 8.0 * 2.0 + 2.0 * 6.0);
+    assert!(insets[2].is_empty());
+}
+
+#[test]
+fn insets_of_a_square_with_a_hole() {
+    let inset = skeleton_inset(&square_with_hole(), 0.5);
+    assert_eq!(validate(&inset), Vec::new());
+    assert_eq!(inset.len(), 2);
+    assert_close(polygen_signed_area(&inset), 81.0 - 49.0);
+    // 环宽2，缩进1时两侧的波前相遇，整个缩没
+    assert!(skeleton_inset(&square_with_hole(), 1.5).is_empty());
+}
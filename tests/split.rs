@@ -0,0 +1,104 @@
+use cg::measure::polygen_signed_area;
+use cg::split::{clip_half_plane, split_by_line, split_by_polyline};
+use cg::valid::validate;
+use cg::{Point, Polygen};
+
+mod common;
+use common::{assert_close, hole, polyline, rect};
+
+#[test]
+fn partial_cut_is_ignored() {
+    // 从左边切进去一半就停下，没有分开任何区域
+    let square = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    let split = split_by_polyline(&square, &polyline(&[[-1.0, 5.0], [5.0, 5.0]]));
+    assert_eq!(split.pieces.len(), 1);
+    assert_close(polygen_signed_area(&split.pieces[0]), 100.0);
+    assert_eq!(split.ignored.len(), 1);
+    let ignored = &split.ignored[0];
+    let mut ends = [ignored[0].position, ignored[ignored.len() - 1].position];
+    ends.sort_by(|a, b| a[0].total_cmp(&b[0]));
+    assert_eq!(ends, [[0.0, 5.0], [5.0, 5.0]]);
+}
+
+#[test]
+fn zig_zag_cut_crosses_many_times() {
+    // 折线上下穿过矩形三次，切成四块；伸出矩形之外的部分没有影响
+    let square = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    let line = polyline(&[[2.0, -1.0], [4.0, 11.0], [6.0, -1.0], [8.0, 11.0]]);
+    let split = split_by_polyline(&square, &line);
+    assert!(split.ignored.is_empty());
+    assert_eq!(split.pieces.len(), 4);
+    for piece in &split.pieces {
+        assert_eq!(validate(piece), Vec::new());
+        assert!(polygen_signed_area(piece) > 0.0);
+    }
+    assert_close(split.pieces.iter().map(polygen_signed_area).sum(), 100.0);
+}
+
+#[test]
+fn cut_through_a_hole() {
+    // 穿过洞的直线把带洞的正方形切成两个 U 形
+    let polygen = vec![rect(0.0, 0.0, 10.0, 10.0), hole(3.0, 3.0, 7.0, 7.0)];
+    let split = split_by_polyline(&polygen, &polyline(&[[5.0, -1.0], [5.0, 11.0]]));
+    assert!(split.ignored.is_empty());
+    assert_eq!(split.pieces.len(), 2);
+    for piece in &split.pieces {
+        assert_eq!(piece.len(), 1);
+        assert_close(polygen_signed_area(piece), 42.0);
+    }
+}
+
+fn total_area(pieces: &[Polygen]) -> f32 {
+    pieces.iter().map(polygen_signed_area).sum()
+}
+
+#[test]
+fn line_split_areas_sum_to_the_input() {
+    let with_hole = vec![rect(0.0, 0.0, 10.0, 10.0), hole(3.0, 3.0, 7.0, 7.0)];
+    // 竖直向下的直线，左侧（orient > 0）是 x > 4 的一侧
+    let (left, right) = split_by_line(&with_hole, Point::new([4.0, -1.0]), Point::new([4.0, 11.0]));
+    assert_close(total_area(&left), 48.0);
+    assert_close(total_area(&right), 36.0);
+    assert_eq!(left.len(), 1);
+    assert_eq!(right.len(), 1);
+
+    for &(a, b) in &[([0.0, 1.0], [10.0, 8.0]), ([-3.0, 12.0], [11.0, -2.0]), ([5.5, 0.0], [4.5, 10.0])] {
+        let (left, right) = split_by_line(&with_hole, Point::new(a), Point::new(b));
+        assert_close(total_area(&left) + total_area(&right), 84.0);
+        for piece in left.iter().chain(&right) {
+            assert_eq!(validate(piece), Vec::new());
+        }
+        assert_eq!(clip_half_plane(&with_hole, Point::new(a), Point::new(b)), left);
+    }
+}
+
+#[test]
+fn line_that_misses_returns_the_polygon_whole() {
+    let square = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    let (left, right) = split_by_line(&square, Point::new([20.0, 0.0]), Point::new([20.0, 10.0]));
+    assert!(left.is_empty());
+    assert_eq!(right.len(), 1);
+    assert_close(polygen_signed_area(&right[0]), 100.0);
+    // 反过来走，整块都在左侧
+    let (left, right) = split_by_line(&square, Point::new([20.0, 10.0]), Point::new([20.0, 0.0]));
+    assert!(right.is_empty());
+    assert_eq!(left.len(), 1);
+    assert!(clip_half_plane(&square, Point::new([20.0, 0.0]), Point::new([20.0, 10.0])).is_empty());
+}
+
+#[test]
+fn line_through_vertices() {
+    let square = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    // 沿对角线切成两个三角形
+    let (left, right) = split_by_line(&square, Point::new([0.0, 0.0]), Point::new([10.0, 10.0]));
+    for pieces in &[left, right] {
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].len(), 1);
+        assert_eq!(pieces[0][0].len(), 4);
+        assert_close(polygen_signed_area(&pieces[0]), 50.0);
+    }
+    // 只擦过一个角，整块在同一侧
+    let (left, right) = split_by_line(&square, Point::new([-5.0, 5.0]), Point::new([5.0, 15.0]));
+    assert_eq!(left.len() + right.len(), 1);
+    assert_close(total_area(&left) + total_area(&right), 100.0);
+}
@@ -0,0 +1,63 @@
+use cg::measure::polygen_signed_area;
+use cg::stroke::{stroke, LineCap, LineJoin, StrokeOptions};
+use cg::valid::validate;
+use cg::Polygen;
+
+mod common;
+use common::{assert_close, polyline};
+
+fn options(cap: LineCap, join: LineJoin) -> StrokeOptions {
+    StrokeOptions { width: 2.0, cap, join, tolerance: 0.01 }
+}
+
+fn has_vertex(polygen: &Polygen, p: [f32; 2]) -> bool {
+    polygen.iter().flatten().any(|q| q.position == p)
+}
+
+#[test]
+fn overlapping_u_turn_is_valid() {
+    // 两条平行段相距1，比线宽2小，轮廓互相重叠
+    let hairpin = polyline(&[[0.0, 0.0], [10.0, 0.0], [10.0, 1.0], [0.0, 1.0]]);
+    for &join in &[LineJoin::Miter { limit: 4.0 }, LineJoin::Round, LineJoin::Bevel] {
+        for &cap in &[LineCap::Butt, LineCap::Round, LineCap::Square] {
+            let outline = stroke(&hairpin, &options(cap, join));
+            assert_eq!(validate(&outline), Vec::new(), "{:?} {:?}", cap, join);
+            assert_eq!(outline.len(), 1, "{:?} {:?}", cap, join);
+            assert!(polygen_signed_area(&outline) > 0.0);
+        }
+    }
+    // 原路折返
+    let back = polyline(&[[0.0, 0.0], [10.0, 0.0], [0.0, 0.0]]);
+    let outline = stroke(&back, &options(LineCap::Round, LineJoin::Round));
+    assert_eq!(validate(&outline), Vec::new());
+    assert_eq!(outline.len(), 1);
+}
+
+#[test]
+fn square_cap_adds_half_a_width_at_each_end() {
+    let line = polyline(&[[0.0, 0.0], [10.0, 0.0]]);
+    let butt = polygen_signed_area(&stroke(&line, &options(LineCap::Butt, LineJoin::Bevel)));
+    let square = polygen_signed_area(&stroke(&line, &options(LineCap::Square, LineJoin::Bevel)));
+    assert_close(butt, 20.0);
+    // 两端各多出 (线宽/2) × 线宽
+    assert_close(square - butt, 2.0 * 1.0 * 2.0);
+    // 圆帽介于两者之间：两个半圆合起来是一个整圆
+    let round = polygen_signed_area(&stroke(&line, &options(LineCap::Round, LineJoin::Bevel)));
+    assert!((round - butt - std::f32::consts::PI).abs() < 0.05, "{}", round - butt);
+}
+
+#[test]
+fn miter_over_the_limit_falls_back_to_bevel() {
+    // 直角转弯，尖角长度与线宽之比为 √2
+    let corner = polyline(&[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]]);
+    let bevel = stroke(&corner, &options(LineCap::Butt, LineJoin::Bevel));
+    let miter = stroke(&corner, &options(LineCap::Butt, LineJoin::Miter { limit: 4.0 }));
+    let limited = stroke(&corner, &options(LineCap::Butt, LineJoin::Miter { limit: 1.4 }));
+    // 两段各 20，重叠 1，斜角补上半个单位正方形，尖角补上整个
+    assert_close(polygen_signed_area(&bevel), 39.5);
+    assert_close(polygen_signed_area(&miter), 40.0);
+    assert!(has_vertex(&miter, [11.0, -1.0]));
+    assert_close(polygen_signed_area(&limited), 39.5);
+    assert!(!has_vertex(&limited, [11.0, -1.0]));
+    assert_eq!(validate(&limited), Vec::new());
+}
@@ -0,0 +1,76 @@
+use cg::boolean::BooleanOp;
+use cg::measure::polygen_area;
+use cg::tagged::{boolean_tagged, TaggedPolygen, TaggedResult};
+
+mod common;
+use common::{assert_close, hole, rect};
+
+// 按面积排序，便于逐个核对
+fn sorted<'a, S, C>(mut results: Vec<TaggedResult<'a, S, C>>) -> Vec<TaggedResult<'a, S, C>> {
+    results.sort_by(|a, b| polygen_area(&a.polygen).total_cmp(&polygen_area(&b.polygen)));
+    results
+}
+
+#[test]
+fn overlapping_parcels_keep_their_tags() {
+    let parcels = TaggedPolygen::new(vec![rect(0.0, 0.0, 10.0, 10.0), rect(20.0, 0.0, 30.0, 10.0)], vec!["a", "b"]);
+    let zone = TaggedPolygen::uniform(vec![rect(5.0, 2.0, 25.0, 8.0)], 7);
+
+    let pieces = boolean_tagged(&parcels, &zone, BooleanOp::Intersection);
+    assert_eq!(pieces.len(), 2);
+    for piece in &pieces {
+        assert_close(polygen_area(&piece.polygen), 30.0);
+        assert_eq!(piece.subject_rings.len(), 1);
+        assert_eq!(piece.clip_data, vec![&7]);
+        let left = piece.polygen[0].iter().all(|p| p.position[0] <= 10.0);
+        assert_eq!(piece.subject_data, vec![if left { &"a" } else { &"b" }]);
+    }
+
+    let union = boolean_tagged(&parcels, &zone, BooleanOp::Union);
+    assert_eq!(union.len(), 1);
+    assert_eq!(union[0].subject_rings, vec![0, 1]);
+    assert_eq!(union[0].clip_rings, vec![0]);
+}
+
+#[test]
+fn nested_inputs_without_shared_boundary() {
+    let county = TaggedPolygen::uniform(vec![rect(0.0, 0.0, 100.0, 100.0)], "county");
+    let site = TaggedPolygen::uniform(vec![rect(40.0, 40.0, 60.0, 60.0)], "site");
+
+    // 交集的边界全部来自 site，county 由包含结果的最小外环找到
+    let intersection = boolean_tagged(&county, &site, BooleanOp::Intersection);
+    assert_eq!(intersection.len(), 1);
+    assert_close(polygen_area(&intersection[0].polygen), 400.0);
+    assert_eq!(intersection[0].subject_data, vec![&"county"]);
+    assert_eq!(intersection[0].clip_data, vec![&"site"]);
+
+    // 并集的边界全部来自 county，site 整个落在结果内部
+    let union = boolean_tagged(&county, &site, BooleanOp::Union);
+    assert_eq!(union.len(), 1);
+    assert_close(polygen_area(&union[0].polygen), 10000.0);
+    assert_eq!(union[0].subject_data, vec![&"county"]);
+    assert_eq!(union[0].clip_data, vec![&"site"]);
+}
+
+#[test]
+fn island_in_a_hole_is_not_tagged_with_the_shell() {
+    let land = TaggedPolygen::new(vec![rect(0.0, 0.0, 100.0, 100.0), hole(20.0, 20.0, 80.0, 80.0)], vec!["land", "lake"]);
+    let islands = TaggedPolygen::new(vec![rect(40.0, 40.0, 60.0, 60.0), rect(5.0, 5.0, 15.0, 15.0)], vec!["island", "pier"]);
+
+    let union = sorted(boolean_tagged(&land, &islands, BooleanOp::Union));
+    assert_eq!(union.len(), 2);
+    // 洞里的小岛不在 land 内部，也没有 land 的边界
+    assert_close(polygen_area(&union[0].polygen), 400.0);
+    assert!(union[0].subject_rings.is_empty());
+    assert_eq!(union[0].clip_data, vec![&"island"]);
+    // 带洞的陆地包住了 pier
+    assert_close(polygen_area(&union[1].polygen), 6400.0);
+    assert_eq!(union[1].subject_rings, vec![0, 1]);
+    assert_eq!(union[1].clip_data, vec![&"pier"]);
+
+    let intersection = boolean_tagged(&land, &islands, BooleanOp::Intersection);
+    assert_eq!(intersection.len(), 1);
+    assert_close(polygen_area(&intersection[0].polygen), 100.0);
+    assert_eq!(intersection[0].subject_data, vec![&"land"]);
+    assert_eq!(intersection[0].clip_data, vec![&"pier"]);
+}
@@ -0,0 +1,87 @@
+use cg::measure::{path_signed_area, polygen_signed_area};
+use cg::trapezoid::{monotone_decomposition, trapezoidal_decomposition};
+use cg::{EdgeRef, Path, Point, Polygen};
+
+mod common;
+use common::{assert_close, hole, rect, ring};
+
+// 上下两侧都是锯齿的凹多边形，带两个洞
+fn comb_with_holes() -> Polygen {
+    vec![
+        ring(&[[0.0, 0.0], [4.0, 6.0], [8.0, 0.0], [12.0, 6.0], [16.0, 0.0], [16.0, 14.0], [8.0, 10.0], [0.0, 14.0]], true),
+        hole(6.0, 7.0, 10.0, 9.0),
+        hole(1.0, 8.0, 3.0, 10.0),
+    ]
+}
+
+// 点 p 在边 edge 所在的直线上
+fn assert_on_edge(polygen: &Polygen, edge: EdgeRef, p: Point) {
+    let path = &polygen[edge.ring];
+    let (a, b) = (path[edge.edge].position, path[edge.edge + 1].position);
+    let p = p.position;
+    let cross = (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0]);
+    assert!(cross.abs() < 1e-3, "{:?} 不在边 {:?} 上", p, edge);
+}
+
+// 沿回路走一圈，y的变化方向（忽略水平段）只改变两次
+fn is_y_monotone(path: &Path) -> bool {
+    let n = path.len() - 1;
+    let directions: Vec<bool> = (0..n)
+        .map(|i| path[(i + 1) % n].position[1] - path[i].position[1])
+        .filter(|&dy| dy != 0.0)
+        .map(|dy| dy > 0.0)
+        .collect();
+    (0..directions.len()).filter(|&i| directions[i] != directions[(i + 1) % directions.len()]).count() <= 2
+}
+
+#[test]
+fn trapezoids_tile_the_polygon_with_holes() {
+    let polygen = comb_with_holes();
+    let trapezoids = trapezoidal_decomposition(&polygen);
+    let total: f32 = trapezoids.iter().map(|t| path_signed_area(&t.path)).sum();
+    assert_close(total, polygen_signed_area(&polygen));
+    for t in &trapezoids {
+        assert!(t.y_min < t.y_max);
+        assert!(path_signed_area(&t.path) > 0.0);
+        // 上下底水平
+        for p in &t.path {
+            let y = p.position[1];
+            assert!(y == t.y_min || y == t.y_max, "{} 不在 {}..{} 的底上", y, t.y_min, t.y_max);
+        }
+        // 每条底上最左的角在左腰上，最右的角在右腰上（退化为三角形时两者是同一点）
+        for &y in &[t.y_min, t.y_max] {
+            let base: Vec<Point> = t.path.iter().copied().filter(|p| p.position[1] == y).collect();
+            let left = *base.iter().min_by(|a, b| a.position[0].total_cmp(&b.position[0])).unwrap();
+            let right = *base.iter().max_by(|a, b| a.position[0].total_cmp(&b.position[0])).unwrap();
+            assert_on_edge(&polygen, t.left, left);
+            assert_on_edge(&polygen, t.right, right);
+        }
+    }
+}
+
+#[test]
+fn monotone_pieces_are_y_monotone_and_tile_the_polygon() {
+    let polygen = comb_with_holes();
+    let pieces = monotone_decomposition(&polygen);
+    let total: f32 = pieces.iter().map(|piece| path_signed_area(&piece.path)).sum();
+    assert_close(total, polygen_signed_area(&polygen));
+    // 锯齿形的外环本身不是y单调的，需要分成多块
+    assert!(!is_y_monotone(&polygen[0]));
+    assert!(pieces.len() > 1);
+    for piece in &pieces {
+        assert!(path_signed_area(&piece.path) > 0.0);
+        assert!(is_y_monotone(&piece.path), "{:?}", piece.path);
+        assert!(!piece.left.is_empty() && !piece.right.is_empty());
+    }
+}
+
+#[test]
+fn convex_polygon_is_a_single_monotone_piece() {
+    let square = vec![rect(0.0, 0.0, 10.0, 10.0)];
+    let trapezoids = trapezoidal_decomposition(&square);
+    assert_eq!(trapezoids.len(), 1);
+    assert_eq!((trapezoids[0].y_min, trapezoids[0].y_max), (0.0, 10.0));
+    let pieces = monotone_decomposition(&square);
+    assert_eq!(pieces.len(), 1);
+    assert_close(path_signed_area(&pieces[0].path), 100.0);
+}
@@ -0,0 +1,74 @@
+use cg::measure::polygen_signed_area;
+use cg::valid::{is_valid, make_valid, validate, FillRule};
+use cg::Polygen;
+
+mod common;
+use common::{assert_close, closed, ring};
+
+// 每个结果都合法，外环逆时针；按面积从小到大返回各个面积
+fn checked_areas(result: &[Polygen]) -> Vec<f32> {
+    let mut areas: Vec<f32> = result.iter().map(|polygen| {
+        assert_eq!(validate(polygen), Vec::new());
+        polygen_signed_area(polygen)
+    }).collect();
+    areas.sort_by(|a, b| a.total_cmp(b));
+    areas
+}
+
+#[test]
+fn bow_tie_under_both_fill_rules() {
+    // 在 (5, 5) 自交，两个三角形的环绕数一正一负
+    let bow_tie = vec![closed(&[[0.0, 0.0], [10.0, 10.0], [10.0, 0.0], [0.0, 10.0]])];
+    assert!(!is_valid(&bow_tie));
+    for &rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+        let areas = checked_areas(&make_valid(&bow_tie, rule));
+        assert_eq!(areas.len(), 2, "{:?}", rule);
+        assert_close(areas[0], 25.0);
+        assert_close(areas[1], 25.0);
+    }
+}
+
+#[test]
+fn pentagram_center_depends_on_fill_rule() {
+    // 五角星中间的五边形环绕数为2
+    let points: Vec<[f32; 2]> = (0..5).map(|k| {
+        let angle = (2 * k % 5) as f32 * std::f32::consts::TAU / 5.0;
+        [10.0 * angle.cos(), 10.0 * angle.sin()]
+    }).collect();
+    let star = vec![closed(&points)];
+    let non_zero = checked_areas(&make_valid(&star, FillRule::NonZero));
+    assert_eq!(non_zero.len(), 1);
+    let even_odd = checked_areas(&make_valid(&star, FillRule::EvenOdd));
+    assert_eq!(even_odd.len(), 5);
+    // 奇偶规则去掉中间的五边形，五个角面积相等
+    for &area in &even_odd { assert_close(area, even_odd[0]); }
+    assert!(even_odd.iter().sum::<f32>() < non_zero[0] - 1.0);
+}
+
+#[test]
+fn ring_touching_itself() {
+    // 回路两次经过 (5, 5)，成为在这一点相接的两个三角形
+    let figure_eight = vec![closed(&[[0.0, 0.0], [5.0, 5.0], [10.0, 0.0], [10.0, 10.0], [5.0, 5.0], [0.0, 10.0]])];
+    assert!(!is_valid(&figure_eight));
+    for &rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+        let areas = checked_areas(&make_valid(&figure_eight, rule));
+        assert_eq!(areas.len(), 2, "{:?}", rule);
+        assert_close(areas[0], 25.0);
+        assert_close(areas[1], 25.0);
+    }
+}
+
+#[test]
+fn hole_crossing_its_shell() {
+    // 顺时针的洞一半在外环里，一半伸出外环：里面的一半挖掉，外面的一半环绕数为 -1，仍是内部
+    let hole = ring(&[[5.0, 3.0], [15.0, 3.0], [15.0, 7.0], [5.0, 7.0]], false);
+    let shell = ring(&[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]], true);
+    let polygen = vec![shell, hole];
+    assert!(!is_valid(&polygen));
+    for &rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+        let areas = checked_areas(&make_valid(&polygen, rule));
+        assert_eq!(areas.len(), 2, "{:?}", rule);
+        assert_close(areas[0], 20.0);
+        assert_close(areas[1], 80.0);
+    }
+}